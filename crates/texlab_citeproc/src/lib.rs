@@ -1,4 +1,4 @@
-mod name;
+pub mod name;
 mod ris;
 
 use self::ris::*;
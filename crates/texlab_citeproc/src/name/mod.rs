@@ -7,7 +7,7 @@ mod parser {
 }
 
 use self::parser::NamesParser;
-use citeproc_io::Name;
+pub use citeproc_io::{Name, PersonName};
 
 pub fn parse(input: &str) -> Vec<Name> {
     let parser = NamesParser::new();
@@ -29,6 +29,11 @@ impl FeatureProvider for BibtexEntryTypeCompletionProvider {
                     }
                     BibtexDeclaration::Entry(entry) => {
                         if contains(&entry.ty, position) {
+                            if entry.left.is_none()
+                                && request.client_capabilities.has_snippet_support()
+                            {
+                                return make_snippets(request, entry.ty.range());
+                            }
                             return make_items(request, entry.ty.range());
                         }
                     }
@@ -55,6 +60,75 @@ fn make_items(request: &FeatureRequest<CompletionParams>, mut range: Range) -> V
     items
 }
 
+fn make_snippets(
+    request: &FeatureRequest<CompletionParams>,
+    mut range: Range,
+) -> Vec<CompletionItem> {
+    range.start.character += 1;
+    let params = BibtexFormattingParams {
+        insert_spaces: false,
+        options: request
+            .options
+            .bibtex
+            .as_ref()
+            .and_then(|opts| opts.formatting.clone())
+            .unwrap_or_default(),
+        ..BibtexFormattingParams::default()
+    };
+
+    let mut items = Vec::new();
+    for ty in &LANGUAGE_DATA.entry_types {
+        let item = match ty.name.as_str() {
+            "preamble" | "string" | "comment" => {
+                let text_edit = TextEdit::new(range, (&ty.name).into());
+                factory::entry_type(request, ty, text_edit)
+            }
+            _ => {
+                let text_edit = TextEdit::new(range, make_snippet(ty, &params));
+                factory::entry_type_snippet(request, ty, text_edit)
+            }
+        };
+        items.push(item);
+    }
+    items
+}
+
+fn make_snippet(ty: &BibtexEntryTypeDoc, params: &BibtexFormattingParams) -> String {
+    let indent = params.indent();
+    let mut snippet = format!("{}{{${{1:key}},", ty.name);
+    if ty.required_fields.is_empty() {
+        snippet.push_str(&format!("\n{}$0\n}}", indent));
+        return snippet;
+    }
+
+    let fields: Vec<&str> = ty
+        .required_fields
+        .iter()
+        .map(|field| field.split('|').next().unwrap())
+        .collect();
+
+    let width = if params.options.align_fields() {
+        fields.iter().map(|field| field.len()).max().unwrap()
+    } else {
+        0
+    };
+
+    for (i, field) in fields.iter().enumerate() {
+        snippet.push_str(&format!(
+            "\n{}{:width$} = {{${}}}",
+            indent,
+            field,
+            i + 2,
+            width = width
+        ));
+        if i + 1 < fields.len() || params.options.trailing_commas() {
+            snippet.push(',');
+        }
+    }
+    snippet.push_str("\n}");
+    snippet
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +183,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn snippet() {
+        let items = test_feature(
+            BibtexEntryTypeCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "@art")],
+                main_file: "foo.bib",
+                position: Position::new(0, 4),
+                client_capabilities: FeatureSpec::snippet_capabilities(),
+                ..FeatureSpec::default()
+            },
+        );
+        let item = items.iter().find(|item| item.label == "article").unwrap();
+        assert_eq!(item.insert_text_format, Some(InsertTextFormat::Snippet));
+        assert_eq!(
+            item.text_edit,
+            Some(TextEdit::new(
+                Range::new_simple(0, 1, 0, 4),
+                "article{${1:key},\n\tauthor = {$2},\n\ttitle = {$3},\n\tjournaltitle = {$4},\n\tyear = {$5},\n}".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn snippet_formatting_options() {
+        let items = test_feature(
+            BibtexEntryTypeCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "@boo")],
+                main_file: "foo.bib",
+                position: Position::new(0, 4),
+                client_capabilities: FeatureSpec::snippet_capabilities(),
+                options: Options {
+                    bibtex: Some(BibtexOptions {
+                        formatting: Some(BibtexFormattingOptions {
+                            align_fields: Some(true),
+                            trailing_commas: Some(false),
+                            ..BibtexFormattingOptions::default()
+                        }),
+                        ..BibtexOptions::default()
+                    }),
+                    ..Options::default()
+                },
+                ..FeatureSpec::default()
+            },
+        );
+        let item = items.iter().find(|item| item.label == "book").unwrap();
+        assert_eq!(
+            item.text_edit.as_ref().map(|edit| edit.new_text.as_str()),
+            Some("book{${1:key},\n\tauthor = {$2},\n\ttitle  = {$3},\n\tyear   = {$4}\n}")
+        );
+    }
+
+    #[test]
+    fn snippet_existing_entry() {
+        let items = test_feature(
+            BibtexEntryTypeCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "@art{foo,}")],
+                main_file: "foo.bib",
+                position: Position::new(0, 4),
+                client_capabilities: FeatureSpec::snippet_capabilities(),
+                ..FeatureSpec::default()
+            },
+        );
+        let item = items.iter().find(|item| item.label == "article").unwrap();
+        assert_eq!(item.insert_text_format, None);
+        assert_eq!(
+            item.text_edit,
+            Some(TextEdit::new(
+                Range::new_simple(0, 1, 0, 4),
+                "article".into()
+            ))
+        );
+    }
+
     #[test]
     fn inside_entry_key() {
         let items = test_feature(
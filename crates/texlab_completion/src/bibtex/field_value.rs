@@ -0,0 +1,427 @@
+use crate::factory;
+use futures_boxed::boxed;
+use std::collections::HashSet;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+const LIBRARY_FIELDS: &[&str] = &[
+    "journal",
+    "journaltitle",
+    "publisher",
+    "booktitle",
+    "series",
+    "keywords",
+];
+
+const MONTHS: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+const LANGUAGES: &[&str] = &[
+    "american",
+    "brazilian",
+    "british",
+    "catalan",
+    "croatian",
+    "czech",
+    "danish",
+    "dutch",
+    "english",
+    "estonian",
+    "finnish",
+    "french",
+    "german",
+    "greek",
+    "hungarian",
+    "italian",
+    "latin",
+    "ngerman",
+    "norwegian",
+    "polish",
+    "portuguese",
+    "russian",
+    "slovak",
+    "slovene",
+    "spanish",
+    "swedish",
+    "turkish",
+    "ukrainian",
+];
+
+const PUBLICATION_STATES: &[&str] = &[
+    "inpreparation",
+    "submitted",
+    "forthcoming",
+    "inpress",
+    "prepublished",
+];
+
+const TYPES: &[&str] = &[
+    "mathesis",
+    "phdthesis",
+    "candthesis",
+    "techreport",
+    "resreport",
+    "software",
+    "datacd",
+    "audiocd",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BibtexFieldValueCompletionProvider;
+
+impl FeatureProvider for BibtexFieldValueCompletionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let position = request.params.text_document_position.position;
+        let (field, content) = match Self::find_content(request, position) {
+            Some(result) => result,
+            None => return Vec::new(),
+        };
+
+        let name = field.name.text().to_lowercase();
+        match name.as_str() {
+            "month" => MONTHS
+                .iter()
+                .map(|month| {
+                    let text_edit = TextEdit::new(content.range(), (*month).into());
+                    factory::field_value(request, (*month).into(), text_edit)
+                })
+                .collect(),
+            "langid" => Self::make_items(request, content, LANGUAGES.iter().map(|s| (*s).into())),
+            "pubstate" => Self::make_items(
+                request,
+                content,
+                PUBLICATION_STATES.iter().map(|s| (*s).into()),
+            ),
+            "type" => Self::make_items(request, content, TYPES.iter().map(|s| (*s).into())),
+            "keywords" => Self::make_keyword_items(request, field, content, position),
+            _ if LIBRARY_FIELDS.contains(&name.as_str()) => {
+                let values = Self::library_values(request, field);
+                Self::make_items(request, content, values.into_iter())
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl BibtexFieldValueCompletionProvider {
+    fn find_content(
+        request: &FeatureRequest<CompletionParams>,
+        position: Position,
+    ) -> Option<(&BibtexField, &BibtexContent)> {
+        let tree = match &request.document().tree {
+            SyntaxTree::Bibtex(tree) => tree,
+            SyntaxTree::Latex(_) => return None,
+        };
+
+        let field = tree
+            .find(position)
+            .into_iter()
+            .find_map(|node| match node {
+                BibtexNode::Field(field) => Some(field),
+                _ => None,
+            })?;
+
+        let content = field.content.as_ref()?;
+        if Self::inner_range(content)?.contains(position) {
+            Some((field, content))
+        } else {
+            None
+        }
+    }
+
+    fn inner_range(content: &BibtexContent) -> Option<Range> {
+        let (left, right) = match content {
+            BibtexContent::BracedContent(content) => (&content.left, &content.right),
+            BibtexContent::QuotedContent(content) => (&content.left, &content.right),
+            BibtexContent::Word(word) => return Some(word.range()),
+            _ => return None,
+        };
+        let end = right
+            .as_ref()
+            .map(BibtexToken::start)
+            .unwrap_or_else(|| content.end());
+        Some(Range::new(left.end(), end))
+    }
+
+    fn make_items<I: Iterator<Item = String>>(
+        request: &FeatureRequest<CompletionParams>,
+        content: &BibtexContent,
+        values: I,
+    ) -> Vec<CompletionItem> {
+        let range = Self::inner_range(content).unwrap();
+        values
+            .map(|value| {
+                let new_text = match content {
+                    BibtexContent::Word(_) => format!("{{{}}}", value),
+                    _ => value.clone(),
+                };
+                factory::field_value(request, value, TextEdit::new(range, new_text))
+            })
+            .collect()
+    }
+
+    fn make_keyword_items(
+        request: &FeatureRequest<CompletionParams>,
+        field: &BibtexField,
+        content: &BibtexContent,
+        position: Position,
+    ) -> Vec<CompletionItem> {
+        let children = match content {
+            BibtexContent::BracedContent(content) => &content.children,
+            BibtexContent::QuotedContent(content) => &content.children,
+            _ => return Self::make_items(request, content, Self::keywords(request, field)),
+        };
+
+        let range = children
+            .iter()
+            .find_map(|child| match child {
+                BibtexContent::Word(word)
+                    if word.token.text() != "," && word.range().contains(position) =>
+                {
+                    Some(word.range())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| Range::new(position, position));
+
+        Self::keywords(request, field)
+            .map(|keyword| {
+                let text_edit = TextEdit::new(range, keyword.clone());
+                factory::field_value(request, keyword, text_edit)
+            })
+            .collect()
+    }
+
+    fn keywords<'a>(
+        request: &'a FeatureRequest<CompletionParams>,
+        field: &BibtexField,
+    ) -> impl Iterator<Item = String> + 'a {
+        let mut keywords = HashSet::new();
+        Self::library_values(request, field)
+            .into_iter()
+            .flat_map(|value| {
+                value
+                    .split(',')
+                    .map(|keyword| keyword.trim().to_owned())
+                    .collect::<Vec<_>>()
+            })
+            .filter(move |keyword| !keyword.is_empty() && keywords.insert(keyword.clone()))
+    }
+
+    fn library_values(
+        request: &FeatureRequest<CompletionParams>,
+        current_field: &BibtexField,
+    ) -> Vec<String> {
+        let name = current_field.name.text().to_lowercase();
+        let mut values = Vec::new();
+        let mut visited = HashSet::new();
+        for document in request.workspace().documents() {
+            let tree = match &document.tree {
+                SyntaxTree::Bibtex(tree) => tree,
+                SyntaxTree::Latex(_) => continue,
+            };
+
+            for entry in tree.entries() {
+                if entry.is_comment() {
+                    continue;
+                }
+
+                for field in &entry.fields {
+                    if field.name.text().to_lowercase() != name
+                        || (document.uri == request.document().uri
+                            && field.range == current_field.range)
+                    {
+                        continue;
+                    }
+
+                    let value = match &field.content {
+                        Some(content @ BibtexContent::BracedContent(_))
+                        | Some(content @ BibtexContent::QuotedContent(_)) => {
+                            let range = Self::inner_range(content).unwrap();
+                            CharStream::extract(&document.text, range)
+                                .split_whitespace()
+                                .collect::<Vec<_>>()
+                                .join(" ")
+                        }
+                        _ => continue,
+                    };
+
+                    if !value.is_empty() && visited.insert(value.clone()) {
+                        values.push(value);
+                    }
+                }
+            }
+        }
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(items: &[CompletionItem]) -> Vec<&str> {
+        items.iter().map(|item| item.label.as_str()).collect()
+    }
+
+    #[test]
+    fn journal() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.bib", "@article{foo, journal = {J}}"),
+                    FeatureSpec::file(
+                        "bar.bib",
+                        "@article{bar, journal = {Journal of\n  Computational Physics}}\n\
+                         @article{baz, journal = \"Acta Numerica\"}",
+                    ),
+                ],
+                main_file: "foo.bib",
+                position: Position::new(0, 26),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            labels(&items),
+            vec!["Journal of Computational Physics", "Acta Numerica"]
+        );
+        assert_eq!(
+            items[0].text_edit,
+            Some(TextEdit::new(
+                Range::new_simple(0, 25, 0, 26),
+                "Journal of Computational Physics".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn publisher_word() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@book{foo, publisher = Spr}\n@book{bar, publisher = {Springer}}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 25),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            items
+                .into_iter()
+                .map(|item| item.text_edit)
+                .collect::<Vec<_>>(),
+            vec![Some(TextEdit::new(
+                Range::new_simple(0, 23, 0, 26),
+                "{Springer}".into()
+            ))]
+        );
+    }
+
+    #[test]
+    fn keywords() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@article{foo, keywords = {bar, q}}\n@article{baz, keywords = {bar, qux}}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 32),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(labels(&items), vec!["bar", "qux"]);
+        assert_eq!(
+            items[1].text_edit,
+            Some(TextEdit::new(Range::new_simple(0, 31, 0, 32), "qux".into()))
+        );
+    }
+
+    #[test]
+    fn month() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "@article{foo, month = {j}}")],
+                main_file: "foo.bib",
+                position: Position::new(0, 24),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(items.len(), 12);
+        assert_eq!(
+            items[0].text_edit,
+            Some(TextEdit::new(Range::new_simple(0, 22, 0, 25), "jan".into()))
+        );
+    }
+
+    #[test]
+    fn pubstate() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "@article{foo, pubstate = {}}")],
+                main_file: "foo.bib",
+                position: Position::new(0, 26),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(labels(&items), PUBLICATION_STATES.to_vec());
+    }
+
+    #[test]
+    fn field_name() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "@article{foo, month = {j}}")],
+                main_file: "foo.bib",
+                position: Position::new(0, 15),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn unknown_field() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@article{foo, title = {b}}\n@article{bar, title = {baz}}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 23),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn latex() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "@article{foo, month = {j}}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 24),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+}
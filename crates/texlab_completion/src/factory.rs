@@ -1,3 +1,4 @@
+use itertools::Itertools;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -23,11 +24,16 @@ pub enum CompletionItemData {
     ColorModel,
     Package,
     Class,
+    ComponentOption,
     EntryType,
     FieldName,
+    FieldValue,
     Citation { uri: Uri, key: String },
     Argument,
+    KeyValKey,
+    KeyValValue,
     GlossaryEntry,
+    Register,
 }
 
 impl Into<serde_json::Value> for CompletionItemData {
@@ -72,6 +78,74 @@ fn supports_images(request: &FeatureRequest<CompletionParams>) -> bool {
         .map_or(true, |formats| formats.contains(&MarkupKind::Markdown))
 }
 
+pub fn supports_argument_snippets(request: &FeatureRequest<CompletionParams>) -> bool {
+    request.client_capabilities.has_snippet_support()
+        && request
+            .options
+            .latex
+            .as_ref()
+            .and_then(|opts| opts.completion.as_ref())
+            .map_or(false, LatexCompletionOptions::argument_snippets)
+}
+
+pub fn argument_snippet<'a, I>(name: &str, parameters: I) -> String
+where
+    I: IntoIterator<Item = &'a [Argument]>,
+{
+    let mut snippet = escape_snippet(name, "$}\\");
+    for (i, arguments) in parameters.into_iter().enumerate() {
+        if arguments.is_empty() {
+            snippet.push_str(&format!("{{${}}}", i + 1));
+        } else {
+            let choices = arguments
+                .iter()
+                .map(|argument| escape_snippet(&argument.name, "$}\\,|"))
+                .join(",");
+            snippet.push_str(&format!("{{${{{}|{}|}}}}", i + 1, choices));
+        }
+    }
+    snippet
+}
+
+pub fn signature_snippet(name: &str, signature: &LatexSignature) -> String {
+    let mut snippet = escape_snippet(name, "$}\\");
+    for (i, parameter) in signature.mandatory_parameters().enumerate() {
+        let (left, right) = match parameter {
+            LatexParameter::Mandatory { delimiters, .. } => (&delimiters.0[..], &delimiters.1[..]),
+            _ => ("{", "}"),
+        };
+        snippet.push_str(&format!(
+            "{}${}{}",
+            escape_snippet(left, "$\\"),
+            i + 1,
+            escape_snippet(right, "$\\")
+        ));
+    }
+    snippet
+}
+
+fn escape_snippet(text: &str, special: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if special.contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+pub fn with_argument_snippet(item: CompletionItem, snippet: String) -> CompletionItem {
+    CompletionItem {
+        text_edit: item
+            .text_edit
+            .as_ref()
+            .map(|edit| TextEdit::new(edit.range, snippet)),
+        insert_text_format: Some(InsertTextFormat::Snippet),
+        ..item
+    }
+}
+
 pub fn command(
     request: &FeatureRequest<CompletionParams>,
     name: String,
@@ -288,6 +362,31 @@ pub fn class(
     }
 }
 
+pub fn component_option(
+    request: &FeatureRequest<CompletionParams>,
+    name: String,
+    component: &str,
+    description: Option<String>,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    let documentation = description.map(|value| {
+        Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::PlainText,
+            value,
+        })
+    });
+
+    CompletionItem {
+        label: name,
+        kind: Some(adjust_kind(request, Structure::Key.completion_kind())),
+        data: Some(CompletionItemData::ComponentOption.into()),
+        detail: Some(component.into()),
+        documentation,
+        text_edit: Some(text_edit),
+        ..CompletionItem::default()
+    }
+}
+
 pub fn citation(
     request: &FeatureRequest<CompletionParams>,
     uri: Uri,
@@ -348,6 +447,17 @@ pub fn entry_type(
     }
 }
 
+pub fn entry_type_snippet(
+    request: &FeatureRequest<CompletionParams>,
+    ty: &BibtexEntryTypeDoc,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    CompletionItem {
+        insert_text_format: Some(InsertTextFormat::Snippet),
+        ..entry_type(request, ty, text_edit)
+    }
+}
+
 pub fn field_name(
     request: &FeatureRequest<CompletionParams>,
     field: &'static BibtexFieldDoc,
@@ -366,6 +476,20 @@ pub fn field_name(
     }
 }
 
+pub fn field_value(
+    request: &FeatureRequest<CompletionParams>,
+    value: String,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    CompletionItem {
+        label: value,
+        kind: Some(adjust_kind(request, Structure::Argument.completion_kind())),
+        data: Some(CompletionItemData::FieldValue.into()),
+        text_edit: Some(text_edit),
+        ..CompletionItem::default()
+    }
+}
+
 pub fn argument(
     request: &FeatureRequest<CompletionParams>,
     name: &'static str,
@@ -382,6 +506,36 @@ pub fn argument(
     }
 }
 
+pub fn key_val_key(
+    request: &FeatureRequest<CompletionParams>,
+    name: &'static str,
+    interface: &'static str,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    CompletionItem {
+        label: name.into(),
+        kind: Some(adjust_kind(request, Structure::Key.completion_kind())),
+        data: Some(CompletionItemData::KeyValKey.into()),
+        detail: Some(interface.into()),
+        text_edit: Some(text_edit),
+        ..CompletionItem::default()
+    }
+}
+
+pub fn key_val_value(
+    request: &FeatureRequest<CompletionParams>,
+    name: &'static str,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    CompletionItem {
+        label: name.into(),
+        kind: Some(adjust_kind(request, Structure::Argument.completion_kind())),
+        data: Some(CompletionItemData::KeyValValue.into()),
+        text_edit: Some(text_edit),
+        ..CompletionItem::default()
+    }
+}
+
 pub fn glossary_entry(
     request: &FeatureRequest<CompletionParams>,
     label: String,
@@ -399,6 +553,28 @@ pub fn glossary_entry(
     }
 }
 
+pub fn register(
+    request: &FeatureRequest<CompletionParams>,
+    name: String,
+    kind: LatexRegisterKind,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    let detail = match kind {
+        LatexRegisterKind::Counter => "counter",
+        LatexRegisterKind::Length => "length",
+        LatexRegisterKind::Box => "box",
+    };
+
+    CompletionItem {
+        label: name,
+        kind: Some(adjust_kind(request, Structure::Register.completion_kind())),
+        data: Some(CompletionItemData::Register.into()),
+        detail: Some(detail.into()),
+        text_edit: Some(text_edit),
+        ..CompletionItem::default()
+    }
+}
+
 fn image_documentation(
     request: &FeatureRequest<CompletionParams>,
     name: &str,
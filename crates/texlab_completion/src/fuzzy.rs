@@ -0,0 +1,131 @@
+const SCORE_MATCH: i32 = 16;
+const SCORE_GAP: i32 = 1;
+const BONUS_CASE: i32 = 1;
+const BONUS_START: i32 = 12;
+const BONUS_BOUNDARY: i32 = 8;
+const BONUS_CONSECUTIVE: i32 = 6;
+
+pub fn score(query: &str, text: &str) -> Option<i32> {
+    let query: Vec<char> = query.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    if query.len() > text.len() {
+        return None;
+    }
+
+    let mut previous: Vec<Option<i32>> = Vec::new();
+    for (i, &query_char) in query.iter().enumerate() {
+        let mut current = vec![None; text.len()];
+        let mut gap = None;
+        for (j, &text_char) in text.iter().enumerate() {
+            if i > 0 && j >= 2 {
+                gap = max(gap, previous[j - 2]).map(|score| score - SCORE_GAP);
+            }
+
+            if !text_char.to_lowercase().eq(query_char.to_lowercase()) {
+                continue;
+            }
+
+            let mut score = SCORE_MATCH + bonus(&text, j);
+            if text_char == query_char {
+                score += BONUS_CASE;
+            }
+
+            current[j] = if i == 0 {
+                Some(score - j as i32 * SCORE_GAP)
+            } else {
+                let consecutive = if j >= 1 {
+                    previous[j - 1].map(|score| score + BONUS_CONSECUTIVE)
+                } else {
+                    None
+                };
+                max(consecutive, gap).map(|best| best + score)
+            };
+        }
+        previous = current;
+    }
+
+    previous
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| score.map(|score| score - (text.len() - j - 1) as i32 * SCORE_GAP))
+        .max()
+}
+
+fn max(a: Option<i32>, b: Option<i32>) -> Option<i32> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+fn bonus(text: &[char], index: usize) -> i32 {
+    if index == 0 {
+        return BONUS_START;
+    }
+
+    let previous = text[index - 1];
+    let current = text[index];
+    if (!previous.is_alphanumeric() && current.is_alphanumeric())
+        || (previous.is_lowercase() && current.is_uppercase())
+    {
+        BONUS_BOUNDARY
+    } else if !previous.is_numeric() && current.is_numeric() {
+        BONUS_BOUNDARY / 2
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query() {
+        assert_eq!(score("", "foo"), Some(0));
+    }
+
+    #[test]
+    fn no_match() {
+        assert_eq!(score("xyz", "foobar"), None);
+        assert_eq!(score("oof", "foo"), None);
+        assert_eq!(score("foobar", "foo"), None);
+    }
+
+    #[test]
+    fn subsequence() {
+        assert!(score("fbr", "foobar").is_some());
+        assert!(score("FB", "foobar").is_some());
+    }
+
+    #[test]
+    fn prefix_before_infix() {
+        assert!(score("sec", "section") > score("sec", "subsection"));
+    }
+
+    #[test]
+    fn exact_before_prefix() {
+        assert!(score("sec", "sec") > score("sec", "section"));
+    }
+
+    #[test]
+    fn consecutive_before_scattered() {
+        assert!(score("tbf", "tbfoo") > score("tbf", "textbf"));
+    }
+
+    #[test]
+    fn word_boundary() {
+        assert!(score("fs", "fig:setup") > score("fs", "figures"));
+        assert!(score("ts", "textSize") > score("ts", "textsize"));
+    }
+
+    #[test]
+    fn case() {
+        assert!(score("Ref", "Ref") > score("Ref", "ref"));
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+const HISTORY_LIMIT: usize = 32;
+
+// Kept below the bonus for an exact match so that recency only decides between similar matches.
+const BONUS_RECENT_MAX: i32 = 8;
+
+#[derive(Debug, Default)]
+pub struct CompletionHistory {
+    labels: Mutex<VecDeque<String>>,
+}
+
+impl CompletionHistory {
+    pub fn push(&self, label: String) {
+        let mut labels = self.labels.lock().unwrap();
+        labels.retain(|other| *other != label);
+        labels.push_front(label);
+        labels.truncate(HISTORY_LIMIT);
+    }
+
+    pub fn bonuses(&self) -> HashMap<String, i32> {
+        let labels = self.labels.lock().unwrap();
+        labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| {
+                let bonus =
+                    BONUS_RECENT_MAX - (i * BONUS_RECENT_MAX as usize / HISTORY_LIMIT) as i32;
+                (label.clone(), bonus)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn most_recent_first() {
+        let history = CompletionHistory::default();
+        history.push("foo".into());
+        history.push("bar".into());
+        history.push("foo".into());
+        let bonuses = history.bonuses();
+        assert_eq!(bonuses.len(), 2);
+        assert!(bonuses["foo"] > bonuses["bar"]);
+    }
+
+    #[test]
+    fn limit() {
+        let history = CompletionHistory::default();
+        for i in 0..=HISTORY_LIMIT {
+            history.push(i.to_string());
+        }
+        let bonuses = history.bonuses();
+        assert_eq!(bonuses.len(), HISTORY_LIMIT);
+        assert!(!bonuses.contains_key("0"));
+        assert!(bonuses
+            .values()
+            .all(|bonus| *bonus > 0 && *bonus <= BONUS_RECENT_MAX));
+    }
+}
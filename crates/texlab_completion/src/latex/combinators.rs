@@ -42,8 +42,35 @@ pub struct ArgumentContext<'a> {
 }
 
 pub async fn argument<'a, I, E, F>(
+    request: &'a FeatureRequest<CompletionParams>,
+    parameters: I,
+    execute: E,
+) -> Vec<CompletionItem>
+where
+    I: Iterator<Item = Parameter<'a>>,
+    E: FnOnce(ArgumentContext<'a>) -> F,
+    F: Future<Output = Vec<CompletionItem>>,
+{
+    group(request, parameters, |command| &command.args, execute).await
+}
+
+pub async fn options<'a, I, E, F>(
+    request: &'a FeatureRequest<CompletionParams>,
+    parameters: I,
+    execute: E,
+) -> Vec<CompletionItem>
+where
+    I: Iterator<Item = Parameter<'a>>,
+    E: FnOnce(ArgumentContext<'a>) -> F,
+    F: Future<Output = Vec<CompletionItem>>,
+{
+    group(request, parameters, |command| &command.options, execute).await
+}
+
+async fn group<'a, I, E, F>(
     request: &'a FeatureRequest<CompletionParams>,
     mut parameters: I,
+    groups: fn(&LatexCommand) -> &Vec<Arc<LatexGroup>>,
     execute: E,
 ) -> Vec<CompletionItem>
 where
@@ -59,7 +86,7 @@ where
                     continue;
                 }
 
-                if let Some(args) = command.args.get(parameter.index) {
+                if let Some(args) = groups(&command).get(parameter.index) {
                     if args.right.is_some() && !args.range().contains_exclusive(position) {
                         continue;
                     }
@@ -139,6 +166,127 @@ where
     argument(request, parameters, execute).await
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct KeyValContext {
+    pub interface: &'static KeyValInterface,
+    pub key: Option<String>,
+    pub range: Range,
+}
+
+pub async fn key_val<E, F>(
+    request: &FeatureRequest<CompletionParams>,
+    execute: E,
+) -> Vec<CompletionItem>
+where
+    E: FnOnce(KeyValContext) -> F,
+    F: Future<Output = Vec<CompletionItem>>,
+{
+    if let SyntaxTree::Latex(tree) = &request.document().tree {
+        let position = request.params.text_document_position.position;
+        if let Some(command) = find_command(tree, position) {
+            for interface in &KEYVAL_DATA.interfaces {
+                let group = match find_key_val_group(interface, &command, position) {
+                    Some(group) => group,
+                    None => continue,
+                };
+
+                let range = Range::new(group.left.end(), position);
+                let text = CharStream::extract(&request.document().text, range);
+                if let Some((key, prefix)) = parse_key_val(&text) {
+                    let start = Position::new(
+                        position.line,
+                        position.character - prefix.chars().count() as u64,
+                    );
+                    let context = KeyValContext {
+                        interface,
+                        key: key.map(ToOwned::to_owned),
+                        range: Range::new(start, position),
+                    };
+                    return execute(context).await;
+                }
+                break;
+            }
+        }
+    }
+    Vec::new()
+}
+
+fn find_key_val_group<'a>(
+    interface: &KeyValInterface,
+    command: &'a LatexCommand,
+    position: Position,
+) -> Option<&'a LatexGroup> {
+    let name = command.name.text();
+    let mut groups = Vec::new();
+    for cmd in interface.commands.iter().filter(|cmd| cmd.name == name) {
+        if cmd.optional {
+            groups.extend(command.options.get(cmd.index));
+        } else {
+            groups.extend(command.args.get(cmd.index));
+        }
+    }
+
+    let has_word = |index: usize, names: &[String]| {
+        command
+            .extract_word(index)
+            .map(|word| names.iter().any(|name| name == word.text()))
+            .unwrap_or(false)
+    };
+
+    if LANGUAGE_DATA
+        .environment_commands
+        .iter()
+        .any(|cmd| cmd.name == name && has_word(cmd.index, &interface.environments))
+    {
+        groups.extend(command.options.first());
+    }
+
+    if LANGUAGE_DATA.include_commands.iter().any(|cmd| {
+        cmd.name == name
+            && cmd.kind == LatexIncludeKind::Package
+            && has_word(cmd.index, &interface.packages)
+    }) {
+        groups.extend(command.options.first());
+    }
+
+    groups
+        .into_iter()
+        .find(|group| {
+            group.left.end() <= position
+                && group
+                    .right
+                    .as_ref()
+                    .map(|right| position <= right.start())
+                    .unwrap_or(true)
+        })
+        .map(|group| &**group)
+}
+
+fn parse_key_val(text: &str) -> Option<(Option<&str>, &str)> {
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            ',' if depth == 0 => start = i + 1,
+            _ => (),
+        }
+    }
+
+    if depth > 0 {
+        return None;
+    }
+
+    let segment = &text[start..];
+    let (key, prefix) = match segment.find('=') {
+        Some(index) => (Some(segment[..index].trim()), &segment[index + 1..]),
+        None => (None, segment),
+    };
+    let prefix = prefix.rsplit('\n').next().unwrap().trim_start();
+    Some((key, prefix))
+}
+
 fn find_command(tree: &LatexSyntaxTree, position: Position) -> Option<Arc<LatexCommand>> {
     let mut nodes = tree.find(position);
     nodes.reverse();
@@ -2,6 +2,7 @@ use super::combinators;
 use crate::factory::{self, LatexComponentId};
 use futures_boxed::boxed;
 use texlab_protocol::*;
+use texlab_syntax::*;
 use texlab_workspace::*;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -16,12 +17,18 @@ impl FeatureProvider for LatexComponentCommandCompletionProvider {
         combinators::command(request, |command| {
             async move {
                 let range = command.short_name_range();
+                let snippets = factory::supports_argument_snippets(request);
+                let mode = current_mode(request);
                 let mut items = Vec::new();
                 for component in COMPONENT_DATABASE.related_components(request.related_documents())
                 {
                     let file_names = component.file_names.iter().map(AsRef::as_ref).collect();
                     let id = LatexComponentId::Component(file_names);
-                    for command in &component.commands {
+                    for command in component
+                        .commands
+                        .iter()
+                        .filter(|command| command.allows(mode))
+                    {
                         let text_edit = TextEdit::new(range, (&command.name).into());
                         let item = factory::command(
                             request,
@@ -31,7 +38,14 @@ impl FeatureProvider for LatexComponentCommandCompletionProvider {
                             text_edit,
                             &id,
                         );
-                        items.push(item);
+
+                        if snippets && !command.parameters.is_empty() {
+                            let params = command.parameters.iter().map(|param| &param.0[..]);
+                            let snippet = factory::argument_snippet(&command.name, params);
+                            items.push(factory::with_argument_snippet(item, snippet));
+                        } else {
+                            items.push(item);
+                        }
                     }
                 }
                 items
@@ -41,6 +55,13 @@ impl FeatureProvider for LatexComponentCommandCompletionProvider {
     }
 }
 
+fn current_mode(request: &FeatureRequest<CompletionParams>) -> LatexMode {
+    match &request.document().tree {
+        SyntaxTree::Latex(tree) => tree.mode(request.params.text_document_position.position),
+        SyntaxTree::Bibtex(_) => LatexMode::Text,
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct LatexComponentEnvironmentCompletionProvider;
 
@@ -121,6 +142,29 @@ mod tests {
         assert!(items.is_empty());
     }
 
+    #[test]
+    fn command_argument_snippet() {
+        let items = test_feature(
+            LatexComponentCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\usepackage{amsfonts}\n$\\mathb$",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(1, 7),
+                options: FeatureSpec::snippet_options(),
+                client_capabilities: FeatureSpec::snippet_capabilities(),
+                ..FeatureSpec::default()
+            },
+        );
+        let item = items.iter().find(|item| item.label == "mathbb").unwrap();
+        assert_eq!(item.insert_text_format, Some(InsertTextFormat::Snippet));
+        let text = &item.text_edit.as_ref().unwrap().new_text;
+        assert!(text.starts_with("mathbb{${1|"));
+        assert!(text.ends_with("|}}"));
+    }
+
     #[test]
     fn command_package() {
         let items = test_feature(
@@ -152,6 +196,42 @@ mod tests {
         assert!(items.iter().any(|item| item.label == "lipsum"));
     }
 
+    #[test]
+    fn command_math_mode() {
+        let items = test_feature(
+            LatexComponentCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\documentclass{book}\n\\usepackage{amsfonts}\n$\\ma$",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 4),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.iter().any(|item| item.label == "mathbb"));
+        assert!(items.iter().all(|item| item.label != "chapter"));
+    }
+
+    #[test]
+    fn command_text_mode() {
+        let items = test_feature(
+            LatexComponentCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\documentclass{book}\n\\usepackage{amsfonts}\n\\ma",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 3),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.iter().any(|item| item.label == "chapter"));
+        assert!(items.iter().all(|item| item.label != "mathbb"));
+    }
+
     #[test]
     fn command_class() {
         let items = test_feature(
@@ -31,6 +31,32 @@ impl FeatureProvider for LatexPackageImportProvider {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexClassOptionProvider;
+
+impl FeatureProvider for LatexClassOptionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        import_option(request, LatexIncludeKind::Class).await
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexPackageOptionProvider;
+
+impl FeatureProvider for LatexPackageOptionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        import_option(request, LatexIncludeKind::Package).await
+    }
+}
+
 async fn import<F>(
     request: &FeatureRequest<CompletionParams>,
     kind: LatexIncludeKind,
@@ -71,9 +97,52 @@ where
     .await
 }
 
+async fn import_option(
+    request: &FeatureRequest<CompletionParams>,
+    kind: LatexIncludeKind,
+) -> Vec<CompletionItem> {
+    let parameters = LANGUAGE_DATA
+        .include_commands
+        .iter()
+        .filter(|cmd| cmd.kind == kind)
+        .map(|cmd| Parameter::new(&cmd.name, 0));
+
+    combinators::options(request, parameters, |context| {
+        async move {
+            let resolver = request.distribution.resolver().await;
+            let mut items = Vec::new();
+            for cmd in LANGUAGE_DATA.include_commands.iter().filter(|cmd| {
+                cmd.kind == kind
+                    && cmd.name == context.command.name.text()
+                    && context.command.has_comma_separated_words(cmd.index)
+            }) {
+                for name in context.command.extract_comma_separated_words(cmd.index) {
+                    for option in COMPONENT_DATABASE.options(name.text(), kind, &resolver) {
+                        let text_edit = TextEdit::new(context.range, option.name.clone());
+                        let item = factory::component_option(
+                            request,
+                            option.name,
+                            name.text(),
+                            option.description,
+                            text_edit,
+                        );
+                        items.push(item);
+                    }
+                }
+            }
+            items
+        }
+    })
+    .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::tempdir;
+    use texlab_distro::Resolver;
 
     #[test]
     fn class() {
@@ -106,4 +175,59 @@ mod tests {
         assert!(items.iter().all(|item| item.label != "beamer"));
         assert!(items.iter().any(|item| item.label == "amsmath"));
     }
+
+    #[test]
+    fn class_option_outside_options() {
+        let items = test_feature(
+            LatexClassOptionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\documentclass[]{article}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 17),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn package_option_without_package() {
+        let items = test_feature(
+            LatexPackageOptionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\usepackage[]")],
+                main_file: "foo.tex",
+                position: Position::new(0, 12),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn package_option() {
+        let directory = tempdir().unwrap();
+        let path = directory.path().join("foo.sty");
+        let text = "\\DeclareOption{bar}{}\n\\DeclareOption{baz}{}\n\\ProcessOptions";
+        fs::write(&path, text).unwrap();
+        let mut files_by_name = HashMap::new();
+        files_by_name.insert("foo.sty".into(), path);
+
+        let items = test_feature(
+            LatexPackageOptionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\usepackage[ba]{foo}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 14),
+                distribution: Box::new(TestDistribution::new(Resolver::new(files_by_name))),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(labels, vec!["bar", "baz"]);
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(0, 12, 0, 14))
+        );
+    }
 }
@@ -40,13 +40,15 @@ impl FeatureProvider for LatexIncludeCompletionProvider {
                     ),
                     None => Range::new(position, position),
                 };
-                let directory = current_directory(&request, &command);
-
-                for entry in WalkDir::new(directory)
-                    .min_depth(1)
-                    .max_depth(1)
-                    .follow_links(false)
+                for entry in current_directories(&request, &command)
                     .into_iter()
+                    .flat_map(|directory| {
+                        WalkDir::new(directory)
+                            .min_depth(1)
+                            .max_depth(1)
+                            .follow_links(false)
+                            .into_iter()
+                    })
                     .filter_map(std::result::Result::ok)
                 {
                     if entry.file_type().is_file() && is_included(&command, &entry.path()) {
@@ -76,11 +78,11 @@ impl FeatureProvider for LatexIncludeCompletionProvider {
     }
 }
 
-fn current_directory(
+fn current_directories(
     request: &FeatureRequest<CompletionParams>,
     command: &LatexCommand,
-) -> PathBuf {
-    let mut path = request
+) -> Vec<PathBuf> {
+    let path = request
         .options
         .latex
         .as_ref()
@@ -94,15 +96,34 @@ fn current_directory(
             Clone::clone,
         );
 
-    path = PathBuf::from(path.to_string_lossy().into_owned().replace('\\', "/"));
+    let mut directories = vec![PathBuf::from(
+        path.to_string_lossy().into_owned().replace('\\', "/"),
+    )];
+
+    let is_graphics = LANGUAGE_DATA
+        .include_commands
+        .iter()
+        .find(|cmd| command.name.text() == cmd.name)
+        .map(|cmd| cmd.kind.is_graphics())
+        .unwrap_or(false);
+
+    if is_graphics {
+        directories.extend(graphics_directories(
+            request.workspace(),
+            &request.document().uri,
+            &request.options,
+        ));
+    }
 
     if let Some(include) = command.extract_word(0) {
-        path.push(include.text());
-        if !include.text().ends_with('/') {
-            path.pop();
+        for path in &mut directories {
+            path.push(include.text());
+            if !include.text().ends_with('/') {
+                path.pop();
+            }
         }
     }
-    path
+    directories
 }
 
 fn is_included(command: &LatexCommand, file: &Path) -> bool {
@@ -0,0 +1,170 @@
+use super::combinators;
+use crate::factory;
+use futures_boxed::boxed;
+use texlab_protocol::*;
+use texlab_syntax::KeyValValueKind;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexKeyValCompletionProvider;
+
+impl FeatureProvider for LatexKeyValCompletionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        combinators::key_val(request, |context| {
+            async move {
+                let mut items = Vec::new();
+                match &context.key {
+                    Some(key) => {
+                        if let Some(key) = context.interface.find_key(key) {
+                            for name in key.value_names() {
+                                let text_edit = TextEdit::new(context.range, name.into());
+                                let item = if key.kind == KeyValValueKind::Color {
+                                    factory::color(request, name, text_edit)
+                                } else {
+                                    factory::key_val_value(request, name, text_edit)
+                                };
+                                items.push(item);
+                            }
+                        }
+                    }
+                    None => {
+                        for key in &context.interface.keys {
+                            let text_edit = TextEdit::new(context.range, key.name.clone());
+                            let item = factory::key_val_key(
+                                request,
+                                &key.name,
+                                &context.interface.name,
+                                text_edit,
+                            );
+                            items.push(item);
+                        }
+                    }
+                }
+                items
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_inside_options() {
+        let items = test_feature(
+            LatexKeyValCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\includegraphics[width=1cm, sc]{foo}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(0, 30),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.iter().any(|item| item.label == "scale"));
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(0, 28, 0, 30))
+        );
+    }
+
+    #[test]
+    fn key_inside_group() {
+        let items = test_feature(
+            LatexKeyValCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\hypersetup{}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 12),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.iter().any(|item| item.label == "colorlinks"));
+    }
+
+    #[test]
+    fn key_inside_package_options() {
+        let items = test_feature(
+            LatexKeyValCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\usepackage[]{geometry}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 12),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.iter().any(|item| item.label == "margin"));
+    }
+
+    #[test]
+    fn color_value_inside_environment() {
+        let items = test_feature(
+            LatexKeyValCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\begin{tikzpicture}[draw=re]\n\\end{tikzpicture}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(0, 27),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.iter().any(|item| item.label == "red"));
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(0, 25, 0, 27))
+        );
+    }
+
+    #[test]
+    fn choice_value() {
+        let items = test_feature(
+            LatexKeyValCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\lstset{numbers=}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 16),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(labels, vec!["none", "left", "right"]);
+    }
+
+    #[test]
+    fn inside_nested_group() {
+        let items = test_feature(
+            LatexKeyValCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\hypersetup{pdftitle={a, }}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 25),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn outside_options() {
+        let items = test_feature(
+            LatexKeyValCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\includegraphics[]{}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 19),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+}
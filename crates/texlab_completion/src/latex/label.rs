@@ -25,8 +25,9 @@ impl FeatureProvider for LatexLabelCompletionProvider {
             async move {
                 let options = &request.options;
                 let source = Self::find_source(&context);
+                let position = request.params.text_document_position.position;
                 let mut items = Vec::new();
-                for document in request.related_documents() {
+                for (i, document) in request.related_documents().iter().enumerate() {
                     let workspace = Arc::clone(&request.view.workspace);
                     let view = DocumentView::new(workspace, Arc::clone(&document), options);
                     let outline = Outline::analyze(&view, options);
@@ -40,6 +41,12 @@ impl FeatureProvider for LatexLabelCompletionProvider {
                             .filter(|label| Self::is_included(tree, label, source))
                         {
                             let outline_context = OutlineContext::parse(&view, &label, &outline);
+                            let proximity = if document.uri == request.document().uri {
+                                (0, Self::distance(label.start().line, position.line))
+                            } else {
+                                (i + 1, label.start().line)
+                            };
+
                             for name in label.names() {
                                 let text = name.text().to_owned();
                                 let text_edit = TextEdit::new(context.range, text.clone());
@@ -49,12 +56,14 @@ impl FeatureProvider for LatexLabelCompletionProvider {
                                     text_edit,
                                     outline_context.as_ref(),
                                 );
-                                items.push(item);
+                                items.push((proximity, item));
                             }
                         }
                     }
                 }
-                items
+
+                items.sort_by_key(|(proximity, _)| *proximity);
+                items.into_iter().map(|(_, item)| item).collect()
             }
         })
         .await
@@ -62,6 +71,14 @@ impl FeatureProvider for LatexLabelCompletionProvider {
 }
 
 impl LatexLabelCompletionProvider {
+    fn distance(line: u64, other: u64) -> u64 {
+        if line > other {
+            line - other
+        } else {
+            other - line
+        }
+    }
+
     fn find_source(context: &ArgumentContext) -> LatexLabelReferenceSource {
         match LANGUAGE_DATA
             .label_commands
@@ -118,6 +135,27 @@ mod tests {
         assert_eq!(labels, vec!["foo", "bar"]);
     }
 
+    #[test]
+    fn nearest_first() {
+        let items = test_feature(
+            LatexLabelCompletionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.tex",
+                        "\\include{bar}\n\\label{foo}\n\n\\label{baz}\n\\ref{}\n\\label{qux}",
+                    ),
+                    FeatureSpec::file("bar.tex", "\\label{bar}"),
+                ],
+                main_file: "foo.tex",
+                position: Position::new(4, 5),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
+        assert_eq!(labels, vec!["baz", "qux", "foo", "bar"]);
+    }
+
     #[test]
     fn outside_of_ref() {
         let items = test_feature(
@@ -8,7 +8,9 @@ pub mod component;
 pub mod glossary;
 pub mod import;
 pub mod include;
+pub mod keyval;
 pub mod label;
+pub mod register;
 pub mod theorem;
 pub mod tikz;
 pub mod user;
@@ -0,0 +1,225 @@
+use super::combinators::{self, Parameter};
+use crate::factory;
+use futures_boxed::boxed;
+use itertools::Itertools;
+use std::sync::Arc;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexRegisterCompletionProvider;
+
+impl FeatureProvider for LatexRegisterCompletionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let parameters = LANGUAGE_DATA
+            .register_commands
+            .iter()
+            .filter(|cmd| cmd.kind == LatexRegisterKind::Counter && !cmd.definition)
+            .map(|cmd| Parameter::new(&cmd.name, cmd.index));
+
+        let mut items = combinators::argument(request, parameters, |context| {
+            async move {
+                let mut items = Vec::new();
+                for name in Self::definitions(request, LatexRegisterKind::Counter) {
+                    let text_edit = TextEdit::new(context.range, name.clone());
+                    let item =
+                        factory::register(request, name, LatexRegisterKind::Counter, text_edit);
+                    items.push(item);
+                }
+                items
+            }
+        })
+        .await;
+
+        items.append(
+            &mut combinators::command(request, |command| {
+                async move {
+                    let range = command.short_name_range();
+                    let mut items = Vec::new();
+                    match Self::enclosing_kind(request, &command) {
+                        Some(kind) => {
+                            for name in Self::definitions(request, kind) {
+                                let name = name[1..].to_owned();
+                                let text_edit = TextEdit::new(range, name.clone());
+                                let item = factory::register(request, name, kind, text_edit);
+                                items.push(item);
+                            }
+                        }
+                        None => {
+                            for name in Self::definitions(request, LatexRegisterKind::Counter) {
+                                let name = format!("the{}", name);
+                                let text_edit = TextEdit::new(range, name.clone());
+                                let item = factory::register(
+                                    request,
+                                    name,
+                                    LatexRegisterKind::Counter,
+                                    text_edit,
+                                );
+                                items.push(item);
+                            }
+                        }
+                    }
+                    items
+                }
+            })
+            .await,
+        );
+        items
+    }
+}
+
+impl LatexRegisterCompletionProvider {
+    fn definitions(
+        request: &FeatureRequest<CompletionParams>,
+        kind: LatexRegisterKind,
+    ) -> Vec<String> {
+        let mut names = Vec::new();
+        for document in request.related_documents() {
+            if let SyntaxTree::Latex(tree) = &document.tree {
+                for register in tree.registers.definitions(kind) {
+                    names.push(register.name().text().to_owned());
+                }
+            }
+        }
+        names.into_iter().unique().collect()
+    }
+
+    fn enclosing_kind(
+        request: &FeatureRequest<CompletionParams>,
+        command: &Arc<LatexCommand>,
+    ) -> Option<LatexRegisterKind> {
+        let tree = match &request.document().tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return None,
+        };
+
+        for parent in &tree.commands {
+            for cmd in &LANGUAGE_DATA.register_commands {
+                if cmd.kind == LatexRegisterKind::Counter
+                    || cmd.definition
+                    || parent.name.text() != cmd.name
+                {
+                    continue;
+                }
+
+                if let Some(arg) = parent.args.get(cmd.index) {
+                    if arg.range().contains(command.start()) {
+                        return Some(cmd.kind);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_inside_argument() {
+        let items = test_feature(
+            LatexRegisterCompletionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.tex", "\\newcounter{foo}\n\\include{bar}"),
+                    FeatureSpec::file("bar.tex", "\\setcounter{}{1}"),
+                ],
+                main_file: "bar.tex",
+                position: Position::new(0, 12),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "foo");
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(0, 12, 0, 12))
+        );
+    }
+
+    #[test]
+    fn counter_value() {
+        let items = test_feature(
+            LatexRegisterCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\newcounter{foo}\n\\newlength{\\bar}\n\\the",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 4),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "thefoo");
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(2, 1, 2, 4))
+        );
+    }
+
+    #[test]
+    fn length_inside_argument() {
+        let items = test_feature(
+            LatexRegisterCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\newlength{\\foo}\n\\newsavebox{\\bar}\n\\setlength{\\f}{1cm}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 13),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "foo");
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(2, 12, 2, 13))
+        );
+    }
+
+    #[test]
+    fn box_inside_argument() {
+        let items = test_feature(
+            LatexRegisterCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\newlength{\\foo}\n\\newsavebox{\\bar}\n\\usebox{\\b}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 10),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "bar");
+    }
+
+    #[test]
+    fn outside_argument() {
+        let items = test_feature(
+            LatexRegisterCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\newcounter{foo}\n\\setcounter{}{1}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(1, 15),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+}
@@ -2,6 +2,7 @@ use super::combinators;
 use crate::factory::{self, LatexComponentId};
 use futures_boxed::boxed;
 use itertools::Itertools;
+use std::collections::HashMap;
 use texlab_protocol::*;
 use texlab_syntax::*;
 use texlab_workspace::*;
@@ -17,6 +18,12 @@ impl FeatureProvider for LatexUserCommandCompletionProvider {
     async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
         combinators::command(request, |current_command| {
             async move {
+                let signatures = if factory::supports_argument_snippets(request) {
+                    Self::signatures(request)
+                } else {
+                    HashMap::new()
+                };
+
                 let mut items = Vec::new();
                 for document in request.related_documents() {
                     if let SyntaxTree::Latex(tree) = &document.tree {
@@ -30,14 +37,24 @@ impl FeatureProvider for LatexUserCommandCompletionProvider {
                                     current_command.short_name_range(),
                                     command.to_owned(),
                                 );
-                                factory::command(
+                                let item = factory::command(
                                     request,
                                     command.to_owned(),
                                     None,
                                     None,
                                     text_edit,
                                     &LatexComponentId::User,
-                                )
+                                );
+
+                                match signatures.get(command) {
+                                    Some(signature)
+                                        if signature.mandatory_parameters().next().is_some() =>
+                                    {
+                                        let snippet = factory::signature_snippet(command, signature);
+                                        factory::with_argument_snippet(item, snippet)
+                                    }
+                                    _ => item,
+                                }
                             })
                             .for_each(|item| items.push(item));
                     }
@@ -49,6 +66,28 @@ impl FeatureProvider for LatexUserCommandCompletionProvider {
     }
 }
 
+impl LatexUserCommandCompletionProvider {
+    fn signatures<'a>(
+        request: &'a FeatureRequest<CompletionParams>,
+    ) -> HashMap<&'a str, &'a LatexSignature> {
+        let mut signatures = HashMap::new();
+        for document in request.related_documents() {
+            if let SyntaxTree::Latex(tree) = &document.tree {
+                for definition in &tree.command_definitions {
+                    let name = &definition.definition.name.text()[1..];
+                    signatures.insert(name, &definition.signature);
+                }
+
+                for definition in &tree.primitive_definitions {
+                    let name = &definition.definition.name.text()[1..];
+                    signatures.insert(name, &definition.signature);
+                }
+            }
+        }
+        signatures
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct LatexUserEnvironmentCompletionProvider;
 
@@ -82,6 +121,18 @@ impl FeatureProvider for LatexUserEnvironmentCompletionProvider {
                                 items.push(item);
                             }
                         }
+
+                        for definition in &tree.environment_definitions {
+                            let name = definition.name().text().to_owned();
+                            let text_edit = TextEdit::new(context.range, name.clone());
+                            let item = factory::environment(
+                                request,
+                                name,
+                                text_edit,
+                                &LatexComponentId::User,
+                            );
+                            items.push(item);
+                        }
                     }
                 }
                 items
@@ -130,6 +181,111 @@ mod tests {
         assert_eq!(labels, vec!["include", "bar"]);
     }
 
+    #[test]
+    fn command_argument_snippet() {
+        let items = test_feature(
+            LatexUserCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\newcommand{\\foo}[2]{#1 #2}\n\\newcommand{\\bar}[2][x]{#1 #2}\n\\f",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 2),
+                options: FeatureSpec::snippet_options(),
+                client_capabilities: FeatureSpec::snippet_capabilities(),
+                ..FeatureSpec::default()
+            },
+        );
+        let foo = items.iter().find(|item| item.label == "foo").unwrap();
+        assert_eq!(foo.insert_text_format, Some(InsertTextFormat::Snippet));
+        assert_eq!(
+            foo.text_edit,
+            Some(TextEdit::new(
+                Range::new_simple(2, 1, 2, 2),
+                "foo{$1}{$2}".into()
+            ))
+        );
+
+        let bar = items.iter().find(|item| item.label == "bar").unwrap();
+        assert_eq!(
+            bar.text_edit.as_ref().map(|edit| edit.new_text.as_str()),
+            Some("bar{$1}")
+        );
+
+        let newcommand = items
+            .iter()
+            .find(|item| item.label == "newcommand")
+            .unwrap();
+        assert_eq!(newcommand.insert_text_format, None);
+    }
+
+    #[test]
+    fn command_argument_snippet_disabled() {
+        let items = test_feature(
+            LatexUserCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\newcommand{\\foo}[2]{#1 #2}\n\\f",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(1, 2),
+                client_capabilities: FeatureSpec::snippet_capabilities(),
+                ..FeatureSpec::default()
+            },
+        );
+        let foo = items.iter().find(|item| item.label == "foo").unwrap();
+        assert_eq!(foo.insert_text_format, None);
+        assert_eq!(
+            foo.text_edit.as_ref().map(|edit| edit.new_text.as_str()),
+            Some("foo")
+        );
+    }
+
+    #[test]
+    fn command_document_signature_snippet() {
+        let items = test_feature(
+            LatexUserCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\NewDocumentCommand{\\foo}{s m O{x} r()}{#2}\n\\f",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(1, 2),
+                options: FeatureSpec::snippet_options(),
+                client_capabilities: FeatureSpec::snippet_capabilities(),
+                ..FeatureSpec::default()
+            },
+        );
+        let foo = items.iter().find(|item| item.label == "foo").unwrap();
+        assert_eq!(
+            foo.text_edit.as_ref().map(|edit| edit.new_text.as_str()),
+            Some("foo{$1}($2)")
+        );
+    }
+
+    #[test]
+    fn command_primitive_definition_snippet() {
+        let items = test_feature(
+            LatexUserCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\def\\foo#1#2{#1 #2}\n\\f")],
+                main_file: "foo.tex",
+                position: Position::new(1, 2),
+                options: FeatureSpec::snippet_options(),
+                client_capabilities: FeatureSpec::snippet_capabilities(),
+                ..FeatureSpec::default()
+            },
+        );
+        let foo = items.iter().find(|item| item.label == "foo").unwrap();
+        assert_eq!(
+            foo.text_edit.as_ref().map(|edit| edit.new_text.as_str()),
+            Some("foo{$1}{$2}")
+        );
+    }
+
     #[test]
     fn environment() {
         let items = test_feature(
@@ -152,4 +308,22 @@ mod tests {
             .collect();
         assert_eq!(labels, vec!["bar"]);
     }
+
+    #[test]
+    fn environment_definition() {
+        let items = test_feature(
+            LatexUserEnvironmentCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\NewDocumentEnvironment{bar}{m}{}{}\n\\newenvironment{baz}{}{}\n\\begin{b}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 8),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
+        assert_eq!(labels, vec!["bar", "baz"]);
+    }
 }
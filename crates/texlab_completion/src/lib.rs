@@ -1,5 +1,7 @@
 mod bibtex;
 mod factory;
+mod fuzzy;
+mod history;
 mod latex;
 mod preselect;
 mod quality;
@@ -9,6 +11,8 @@ pub use self::factory::CompletionItemData;
 use self::bibtex::command::BibtexCommandCompletionProvider;
 use self::bibtex::entry_type::BibtexEntryTypeCompletionProvider;
 use self::bibtex::field_name::BibtexFieldNameCompletionProvider;
+use self::bibtex::field_value::BibtexFieldValueCompletionProvider;
+use self::history::CompletionHistory;
 use self::latex::argument::LatexArgumentCompletionProvider;
 use self::latex::begin_command::LatexBeginCommandCompletionProvider;
 use self::latex::citation::LatexCitationCompletionProvider;
@@ -16,9 +20,11 @@ use self::latex::color::LatexColorCompletionProvider;
 use self::latex::color_model::LatexColorModelCompletionProvider;
 use self::latex::component::*;
 use self::latex::glossary::LatexGlossaryCompletionProvider;
-use self::latex::import::{LatexClassImportProvider, LatexPackageImportProvider};
+use self::latex::import::*;
 use self::latex::include::LatexIncludeCompletionProvider;
+use self::latex::keyval::LatexKeyValCompletionProvider;
 use self::latex::label::LatexLabelCompletionProvider;
+use self::latex::register::LatexRegisterCompletionProvider;
 use self::latex::theorem::LatexTheoremEnvironmentCompletionProvider;
 use self::latex::tikz::*;
 use self::latex::user::*;
@@ -27,46 +33,62 @@ use self::quality::OrderByQualityCompletionProvider;
 use futures_boxed::boxed;
 use itertools::Itertools;
 use std::hash::{Hash, Hasher};
-use texlab_protocol::{CompletionItem, CompletionParams};
+use std::sync::Arc;
+use texlab_protocol::{Command, CompletionItem, CompletionParams};
 use texlab_workspace::*;
 
 pub const COMPLETION_LIMIT: usize = 50;
 
+pub const COMPLETION_ACCEPTED_COMMAND: &str = "texlab.completionAccepted";
+
 type MergeProvider = ConcatProvider<CompletionParams, CompletionItem>;
 
 pub struct CompletionProvider {
     provider: OrderByQualityCompletionProvider<PreselectCompletionProvider<MergeProvider>>,
+    history: Arc<CompletionHistory>,
 }
 
 impl CompletionProvider {
     pub fn new() -> Self {
+        let history = Arc::new(CompletionHistory::default());
         Self {
-            provider: OrderByQualityCompletionProvider::new(PreselectCompletionProvider::new(
-                ConcatProvider::new(vec![
+            provider: OrderByQualityCompletionProvider::new(
+                PreselectCompletionProvider::new(ConcatProvider::new(vec![
                     Box::new(BibtexEntryTypeCompletionProvider),
                     Box::new(BibtexFieldNameCompletionProvider),
+                    Box::new(BibtexFieldValueCompletionProvider),
                     Box::new(BibtexCommandCompletionProvider),
                     Box::new(LatexPgfLibraryCompletionProvider),
                     Box::new(LatexTikzLibraryCompletionProvider),
                     Box::new(LatexColorCompletionProvider),
                     Box::new(LatexColorModelCompletionProvider),
+                    Box::new(LatexKeyValCompletionProvider),
                     Box::new(LatexArgumentCompletionProvider),
                     Box::new(LatexComponentEnvironmentCompletionProvider),
                     Box::new(LatexTheoremEnvironmentCompletionProvider),
                     Box::new(LatexLabelCompletionProvider),
                     Box::new(LatexCitationCompletionProvider),
                     Box::new(LatexGlossaryCompletionProvider),
+                    Box::new(LatexRegisterCompletionProvider),
                     Box::new(LatexIncludeCompletionProvider),
                     Box::new(LatexClassImportProvider),
                     Box::new(LatexPackageImportProvider),
+                    Box::new(LatexClassOptionProvider),
+                    Box::new(LatexPackageOptionProvider),
                     Box::new(LatexBeginCommandCompletionProvider),
                     Box::new(LatexComponentCommandCompletionProvider),
                     Box::new(LatexUserCommandCompletionProvider),
                     Box::new(LatexUserEnvironmentCompletionProvider),
-                ]),
-            )),
+                ])),
+                Arc::clone(&history),
+            ),
+            history,
         }
     }
+
+    pub fn accept(&self, label: String) {
+        self.history.push(label);
+    }
 }
 
 impl Default for CompletionProvider {
@@ -89,6 +111,18 @@ impl FeatureProvider for CompletionProvider {
             .unique()
             .map(|item| item.0)
             .take(COMPLETION_LIMIT)
+            .map(|item| {
+                let arguments = vec![item.label.clone().into()];
+                let command = Command::new(
+                    "Accept completion".into(),
+                    COMPLETION_ACCEPTED_COMMAND.into(),
+                    Some(arguments),
+                );
+                CompletionItem {
+                    command: Some(command),
+                    ..item
+                }
+            })
             .collect()
     }
 }
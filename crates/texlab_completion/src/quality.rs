@@ -1,17 +1,26 @@
+use crate::fuzzy;
+use crate::history::CompletionHistory;
 use futures_boxed::boxed;
 use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
 use texlab_protocol::RangeExt;
 use texlab_protocol::{CompletionItem, CompletionParams, Position};
 use texlab_syntax::*;
 use texlab_workspace::*;
 
+const BONUS_EXACT: i32 = 32;
+const PENALTY_FILTER_TEXT: i32 = 16;
+
 pub struct OrderByQualityCompletionProvider<F> {
     pub provider: F,
+    history: Arc<CompletionHistory>,
 }
 
 impl<F> OrderByQualityCompletionProvider<F> {
-    pub fn new(provider: F) -> Self {
-        Self { provider }
+    pub fn new(provider: F, history: Arc<CompletionHistory>) -> Self {
+        Self { provider, history }
     }
 }
 
@@ -28,8 +37,9 @@ where
             request.document(),
             request.params.text_document_position.position,
         );
+        let bonuses = self.history.bonuses();
         let mut items = self.provider.execute(&request).await;
-        items.sort_by_key(|item| -Self::get_quality(&query, &item));
+        items.sort_by_cached_key(|item| Reverse(Self::get_quality(&query, &item, &bonuses)));
         items
     }
 }
@@ -86,40 +96,89 @@ impl<F> OrderByQualityCompletionProvider<F> {
         }
     }
 
-    fn get_quality(query: &Option<Cow<str>>, item: &CompletionItem) -> i32 {
+    fn get_quality(
+        query: &Option<Cow<str>>,
+        item: &CompletionItem,
+        bonuses: &HashMap<String, i32>,
+    ) -> Option<i32> {
         if item.preselect == Some(true) {
-            return 8;
+            return Some(i32::max_value());
         }
 
         let label = &item.label;
-        if let Some(query) = query {
-            if label == query {
-                return 7;
-            }
+        let quality = match query {
+            Some(query) => Self::get_label_quality(query, label).or_else(|| {
+                let words = item.filter_text.as_ref()?.split_whitespace();
+                let quality = words.filter_map(|word| fuzzy::score(query, word)).max()?;
+                Some(quality - PENALTY_FILTER_TEXT)
+            })?,
+            None => 0,
+        };
+        Some(quality + bonuses.get(label).cloned().unwrap_or(0))
+    }
 
-            if label.to_lowercase() == query.to_lowercase() {
-                return 6;
-            }
+    fn get_label_quality(query: &str, label: &str) -> Option<i32> {
+        let mut quality = fuzzy::score(query, label)?;
+        if label == query {
+            quality += BONUS_EXACT;
+        } else if label.to_lowercase() == query.to_lowercase() {
+            quality += BONUS_EXACT / 2;
+        }
+        Some(quality)
+    }
+}
 
-            if label.starts_with(query.as_ref()) {
-                return 5;
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            if label.to_lowercase().starts_with(&query.to_lowercase()) {
-                return 4;
-            }
+    type Provider = OrderByQualityCompletionProvider<()>;
 
-            if label.contains(query.as_ref()) {
-                return 3;
-            }
+    fn quality(query: &str, item: &CompletionItem, bonuses: &HashMap<String, i32>) -> Option<i32> {
+        Provider::get_quality(&Some(query.into()), item, bonuses)
+    }
 
-            if label.to_lowercase().contains(&query.to_lowercase()) {
-                return 2;
-            }
+    #[test]
+    fn exact_before_fuzzy() {
+        let bonuses = HashMap::new();
+        let exact = CompletionItem::new_simple("sec".into(), "".into());
+        let prefix = CompletionItem::new_simple("section".into(), "".into());
+        let fuzzy = CompletionItem::new_simple("subsection".into(), "".into());
+        let none = CompletionItem::new_simple("label".into(), "".into());
+        assert!(quality("sec", &exact, &bonuses) > quality("sec", &prefix, &bonuses));
+        assert!(quality("sec", &prefix, &bonuses) > quality("sec", &fuzzy, &bonuses));
+        assert_eq!(quality("sec", &none, &bonuses), None);
+    }
 
-            1
-        } else {
-            0
-        }
+    #[test]
+    fn filter_text_words() {
+        let bonuses = HashMap::new();
+        let item = CompletionItem {
+            filter_text: Some("knuth1977 article author Knuth, Donald title Fast".into()),
+            ..CompletionItem::new_simple("knuth1977".into(), "".into())
+        };
+        assert!(quality("donald", &item, &bonuses).is_some());
+        assert!(quality("fast", &item, &bonuses).is_some());
+        assert_eq!(quality("lamport", &item, &bonuses), None);
+    }
+
+    #[test]
+    fn recent() {
+        let history = CompletionHistory::default();
+        history.push("foo2".into());
+        let bonuses = history.bonuses();
+        let other = CompletionItem::new_simple("foo1".into(), "".into());
+        let recent = CompletionItem::new_simple("foo2".into(), "".into());
+        assert!(quality("foo", &recent, &bonuses) > quality("foo", &other, &bonuses));
+    }
+
+    #[test]
+    fn exact_before_recent() {
+        let history = CompletionHistory::default();
+        history.push("section".into());
+        let bonuses = history.bonuses();
+        let exact = CompletionItem::new_simple("sec".into(), "".into());
+        let recent = CompletionItem::new_simple("section".into(), "".into());
+        assert!(quality("sec", &exact, &bonuses) > quality("sec", &recent, &bonuses));
     }
 }
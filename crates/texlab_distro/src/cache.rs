@@ -0,0 +1,97 @@
+use super::Resolver;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const RESOLVER_CACHE_FILE: &str = "resolver.json";
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseTimestamp {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+}
+
+impl DatabaseTimestamp {
+    pub fn read(path: PathBuf) -> Self {
+        let modified = fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        Self { path, modified }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResolverCacheEntry {
+    timestamps: Vec<DatabaseTimestamp>,
+    resolver: Resolver,
+}
+
+pub fn load_resolver(directory: &Path, timestamps: &[DatabaseTimestamp]) -> Option<Resolver> {
+    let bytes = fs::read(directory.join(RESOLVER_CACHE_FILE)).ok()?;
+    let entry: ResolverCacheEntry = serde_json::from_slice(&bytes).ok()?;
+    if entry.timestamps == timestamps {
+        Some(entry.resolver)
+    } else {
+        None
+    }
+}
+
+pub fn store_resolver(directory: &Path, timestamps: Vec<DatabaseTimestamp>, resolver: Resolver) {
+    let entry = ResolverCacheEntry {
+        timestamps,
+        resolver,
+    };
+
+    let result = fs::create_dir_all(directory).and_then(|()| {
+        let json = serde_json::to_vec(&entry).unwrap();
+        fs::write(directory.join(RESOLVER_CACHE_FILE), json)
+    });
+
+    if result.is_err() {
+        warn!(
+            "Could not write resolver cache: {}",
+            directory.to_string_lossy()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    fn resolver() -> Resolver {
+        let mut files_by_name = HashMap::new();
+        files_by_name.insert("article.cls".into(), PathBuf::from("/texmf/article.cls"));
+        Resolver::new(files_by_name)
+    }
+
+    #[test]
+    fn load_stored() {
+        let directory = tempdir().unwrap();
+        let database = directory.path().join("ls-R");
+        fs::write(&database, "").unwrap();
+        let timestamps = vec![DatabaseTimestamp::read(database)];
+        store_resolver(directory.path(), timestamps.clone(), resolver());
+        assert_eq!(
+            load_resolver(directory.path(), &timestamps),
+            Some(resolver())
+        );
+    }
+
+    #[test]
+    fn load_outdated() {
+        let directory = tempdir().unwrap();
+        let database = directory.path().join("ls-R");
+        let timestamps = vec![DatabaseTimestamp::read(database.clone())];
+        store_resolver(directory.path(), timestamps, resolver());
+        fs::write(&database, "").unwrap();
+        let timestamps = vec![DatabaseTimestamp::read(database)];
+        assert_eq!(load_resolver(directory.path(), &timestamps), None);
+    }
+}
@@ -1,3 +1,4 @@
+use super::cache::{self, DatabaseTimestamp};
 use super::language::Language;
 use super::{LoadError, Resolver};
 use std::collections::HashMap;
@@ -6,11 +7,26 @@ use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use tokio::process::Command;
 
-pub async fn parse_database<R>(reader: R) -> Result<Resolver, LoadError>
+pub async fn parse_database<D, R>(
+    cache_directory: Option<&Path>,
+    database_files: D,
+    reader: R,
+) -> Result<Resolver, LoadError>
 where
+    D: Fn(&Path) -> Vec<PathBuf>,
     R: Fn(&Path) -> Result<Vec<PathBuf>, LoadError>,
 {
     let root_directories = root_directories().await?;
+    let timestamps: Vec<_> = root_directories
+        .iter()
+        .flat_map(|directory| database_files(directory))
+        .map(DatabaseTimestamp::read)
+        .collect();
+
+    if let Some(resolver) = cache_directory.and_then(|dir| cache::load_resolver(dir, &timestamps)) {
+        return Ok(resolver);
+    }
+
     let mut files_by_name = HashMap::new();
     for directory in &root_directories {
         let database = reader(directory)?
@@ -31,7 +47,15 @@ where
 
         files_by_name.extend(database);
     }
-    Ok(Resolver::new(files_by_name))
+
+    let resolver = Resolver {
+        files_by_name,
+        databases: timestamps.clone(),
+    };
+    if let Some(cache_directory) = cache_directory {
+        cache::store_resolver(cache_directory, timestamps, resolver.clone());
+    }
+    Ok(resolver)
 }
 
 async fn root_directories() -> Result<Vec<PathBuf>, LoadError> {
@@ -1,3 +1,4 @@
+mod cache;
 mod compile;
 mod kpsewhich;
 mod language;
@@ -5,6 +6,7 @@ mod miktex;
 mod tectonic;
 mod texlive;
 
+pub use self::cache::DatabaseTimestamp;
 pub use self::compile::*;
 pub use self::language::Language;
 
@@ -12,6 +14,7 @@ use self::miktex::Miktex;
 use self::tectonic::Tectonic;
 use self::texlive::Texlive;
 use futures_boxed::boxed;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Stdio;
@@ -55,14 +58,19 @@ impl DistributionKind {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 pub struct Resolver {
     pub files_by_name: HashMap<String, PathBuf>,
+    #[serde(default)]
+    pub databases: Vec<DatabaseTimestamp>,
 }
 
 impl Resolver {
     pub fn new(files_by_name: HashMap<String, PathBuf>) -> Self {
-        Self { files_by_name }
+        Self {
+            files_by_name,
+            databases: Vec::new(),
+        }
     }
 }
 
@@ -102,11 +110,11 @@ pub trait Distribution: Send + Sync {
 }
 
 impl dyn Distribution {
-    pub async fn detect() -> Box<Self> {
+    pub async fn detect(cache_directory: Option<PathBuf>) -> Box<Self> {
         let kind = DistributionKind::detect().await;
         let distro: Box<Self> = match kind {
-            DistributionKind::Texlive => Box::new(Texlive::new()),
-            DistributionKind::Miktex => Box::new(Miktex::new()),
+            DistributionKind::Texlive => Box::new(Texlive::new(cache_directory)),
+            DistributionKind::Miktex => Box::new(Miktex::new(cache_directory)),
             DistributionKind::Tectonic => Box::new(Tectonic::new()),
             DistributionKind::Unknown => Box::new(UnknownDistribution::new()),
         };
@@ -14,11 +14,15 @@ use std::sync::Arc;
 #[derive(Debug, Default)]
 pub struct Miktex {
     resolver: Mutex<Arc<Resolver>>,
+    cache_directory: Option<PathBuf>,
 }
 
 impl Miktex {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(cache_directory: Option<PathBuf>) -> Self {
+        Self {
+            resolver: Mutex::default(),
+            cache_directory,
+        }
     }
 }
 
@@ -36,7 +40,13 @@ impl Distribution for Miktex {
 
     #[boxed]
     async fn load(&self) -> Result<(), LoadError> {
-        let resolver = Arc::new(kpsewhich::parse_database(read_database).await?);
+        let resolver = kpsewhich::parse_database(
+            self.cache_directory.as_deref(),
+            database_files,
+            read_database,
+        )
+        .await?;
+        let resolver = Arc::new(resolver);
         let mut resolver_guard = self.resolver.lock().await;
         mem::replace(&mut *resolver_guard, resolver);
         Ok(())
@@ -56,20 +66,26 @@ const FNDB_TABLE_POINTER_OFFSET: usize = 4 * FNDB_WORD_SIZE;
 const FNDB_TABLE_SIZE_OFFSET: usize = 6 * FNDB_WORD_SIZE;
 const FNDB_ENTRY_SIZE: usize = 4 * FNDB_WORD_SIZE;
 
-fn read_database(directory: &Path) -> Result<Vec<PathBuf>, LoadError> {
+fn database_files(directory: &Path) -> Vec<PathBuf> {
     let database_directory = directory.join(DATABASE_PATH);
     if !database_directory.exists() {
-        return Ok(Vec::new());
+        return Vec::new();
     }
 
-    let mut database = Vec::new();
-    let files = fs::read_dir(database_directory)
+    let mut files: Vec<_> = fs::read_dir(database_directory)
         .expect("Could not traverse database directory")
         .filter_map(|x| x.ok())
-        .filter(|x| x.path().extension().and_then(OsStr::to_str) == Some("fndb-5"));
+        .map(|x| x.path())
+        .filter(|x| x.extension().and_then(OsStr::to_str) == Some("fndb-5"))
+        .collect();
+    files.sort();
+    files
+}
 
-    for file in files {
-        let bytes = fs::read(file.path()).expect("Could not read fndb file");
+fn read_database(directory: &Path) -> Result<Vec<PathBuf>, LoadError> {
+    let mut database = Vec::new();
+    for file in database_files(directory) {
+        let bytes = fs::read(file).expect("Could not read fndb file");
         database.extend(parse_database(&bytes).map_err(|_| LoadError::CorruptFileDatabase)?);
     }
 
@@ -13,11 +13,15 @@ use std::sync::Arc;
 #[derive(Debug, Default)]
 pub struct Texlive {
     resolver: Mutex<Arc<Resolver>>,
+    cache_directory: Option<PathBuf>,
 }
 
 impl Texlive {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(cache_directory: Option<PathBuf>) -> Self {
+        Self {
+            resolver: Mutex::default(),
+            cache_directory,
+        }
     }
 }
 
@@ -35,7 +39,13 @@ impl Distribution for Texlive {
 
     #[boxed]
     async fn load(&self) -> Result<(), LoadError> {
-        let resolver = Arc::new(kpsewhich::parse_database(read_database).await?);
+        let resolver = kpsewhich::parse_database(
+            self.cache_directory.as_deref(),
+            database_files,
+            read_database,
+        )
+        .await?;
+        let resolver = Arc::new(resolver);
         let mut resolver_guard = self.resolver.lock().await;
         mem::replace(&mut *resolver_guard, resolver);
         Ok(())
@@ -50,6 +60,10 @@ impl Distribution for Texlive {
 
 const DATABASE_PATH: &'static str = "ls-R";
 
+fn database_files(directory: &Path) -> Vec<PathBuf> {
+    vec![directory.join(DATABASE_PATH)]
+}
+
 fn read_database(directory: &Path) -> Result<Vec<PathBuf>, LoadError> {
     let file = directory.join(DATABASE_PATH);
     if !file.is_file() {
@@ -19,20 +19,29 @@ impl FeatureProvider for LatexCitationHoverProvider {
     ) -> Option<Hover> {
         let (tree, entry) = Self::get_entry(request)?;
         if entry.is_comment() {
-            None
-        } else {
-            let key = entry.key.as_ref().unwrap().text();
-            match render_citation(&tree, key) {
-                Some(markdown) => Some(Hover {
-                    contents: HoverContents::Markup(markdown),
-                    range: None,
-                }),
-                None => {
-                    warn!("Failed to render entry: {}", key);
-                    None
-                }
-            }
+            return None;
         }
+
+        let key = entry.key.as_ref().unwrap().text();
+        let citation = render_citation(&tree, key).map(|markdown| markdown.value);
+        if citation.is_none() {
+            warn!("Failed to render entry: {}", key);
+        }
+
+        let value = match (citation, Self::render_inherited_fields(request, entry)) {
+            (Some(citation), Some(fields)) => format!("{}\n\n---\n\n{}", citation, fields),
+            (Some(citation), None) => citation,
+            (None, Some(fields)) => fields,
+            (None, None) => return None,
+        };
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: None,
+        })
     }
 }
 
@@ -55,6 +64,43 @@ impl LatexCitationHoverProvider {
         None
     }
 
+    fn render_inherited_fields(
+        request: &FeatureRequest<TextDocumentPositionParams>,
+        entry: &BibtexEntry,
+    ) -> Option<String> {
+        let trees: Vec<&BibtexSyntaxTree> = request
+            .related_documents()
+            .iter()
+            .filter_map(|document| match &document.tree {
+                SyntaxTree::Bibtex(tree) => Some(tree),
+                SyntaxTree::Latex(_) => None,
+            })
+            .collect();
+
+        let fields = inherited_fields(&trees, entry);
+        if fields.is_empty() {
+            return None;
+        }
+
+        let mut markdown = String::from("Inherited fields:\n");
+        for inherited in fields {
+            let content = inherited
+                .field
+                .content
+                .as_ref()
+                .map(BibtexContent::text)
+                .unwrap_or_default();
+            let parent = inherited.parent.key.as_ref().map_or("", BibtexToken::text);
+            markdown.push_str(&format!(
+                "\n- `{}` = {} (from `{}`)",
+                inherited.field.name.text(),
+                content,
+                parent
+            ));
+        }
+        Some(markdown)
+    }
+
     fn get_key(request: &FeatureRequest<TextDocumentPositionParams>) -> Option<&str> {
         match &request.document().tree {
             SyntaxTree::Latex(tree) => tree
@@ -76,3 +122,51 @@ impl LatexCitationHoverProvider {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::Position;
+
+    #[test]
+    fn crossref_fields() {
+        let spec = FeatureSpec {
+            files: vec![
+                FeatureSpec::file("foo.tex", "\\bibliography{foo,bar}"),
+                FeatureSpec::file("foo.bib", "@inproceedings{foo, crossref = {bar}}"),
+                FeatureSpec::file(
+                    "bar.bib",
+                    "@proceedings{bar, booktitle = {Baz Qux}, year = 2020}",
+                ),
+            ],
+            main_file: "foo.bib",
+            position: Position::new(0, 16),
+            ..FeatureSpec::default()
+        };
+        let request: FeatureRequest<TextDocumentPositionParams> = spec.into();
+        let (_, entry) = LatexCitationHoverProvider::get_entry(&request).unwrap();
+        assert_eq!(
+            LatexCitationHoverProvider::render_inherited_fields(&request, entry),
+            Some(
+                "Inherited fields:\n\n- `booktitle` = Baz Qux (from `bar`)\n- `year` = 2020 (from `bar`)"
+                    .into()
+            )
+        );
+    }
+
+    #[test]
+    fn no_inherited_fields() {
+        let spec = FeatureSpec {
+            files: vec![FeatureSpec::file("foo.bib", "@article{foo, year = 2020}")],
+            main_file: "foo.bib",
+            position: Position::new(0, 10),
+            ..FeatureSpec::default()
+        };
+        let request: FeatureRequest<TextDocumentPositionParams> = spec.into();
+        let (_, entry) = LatexCitationHoverProvider::get_entry(&request).unwrap();
+        assert_eq!(
+            LatexCitationHoverProvider::render_inherited_fields(&request, entry),
+            None
+        );
+    }
+}
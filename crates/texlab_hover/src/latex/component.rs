@@ -29,9 +29,93 @@ impl FeatureProvider for LatexComponentHoverProvider {
                             });
                         }
                     }
+
+                    if let Some(option) = find_option(include, request.params.position) {
+                        let resolver = request.distribution.resolver().await;
+                        let documentation = include.paths().into_iter().find_map(|path| {
+                            COMPONENT_DATABASE.option_documentation(
+                                path.text(),
+                                include.kind,
+                                option.text(),
+                                &resolver,
+                            )
+                        })?;
+                        return Some(Hover {
+                            contents: HoverContents::Markup(documentation),
+                            range: Some(option.range()),
+                        });
+                    }
                 }
             }
         }
         None
     }
 }
+
+fn find_option(include: &LatexInclude, position: Position) -> Option<&LatexToken> {
+    let options = include.command.options.first()?;
+    for child in &options.children {
+        if let LatexContent::Text(text) = child {
+            for word in &text.words {
+                if word.range().contains(position) {
+                    return Some(word);
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::tempdir;
+    use texlab_distro::Resolver;
+
+    #[test]
+    fn package_option() {
+        let directory = tempdir().unwrap();
+        let path = directory.path().join("foo.sty");
+        let text = "% Enable the bar mode.\n\\DeclareOption{bar}{}\n\\ProcessOptions";
+        fs::write(&path, text).unwrap();
+        let mut files_by_name = HashMap::new();
+        files_by_name.insert("foo.sty".into(), path);
+
+        let hover = test_feature(
+            LatexComponentHoverProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\usepackage[bar]{foo}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 14),
+                distribution: Box::new(TestDistribution::new(Resolver::new(files_by_name))),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            hover,
+            Some(Hover {
+                contents: HoverContents::Markup(MarkupContent {
+                    kind: MarkupKind::PlainText,
+                    value: "Enable the bar mode.".into(),
+                }),
+                range: Some(Range::new_simple(0, 12, 0, 15)),
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_package_option() {
+        let hover = test_feature(
+            LatexComponentHoverProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\usepackage[bar]{foo}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 14),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(hover, None);
+    }
+}
@@ -3,3 +3,4 @@ pub mod component;
 pub mod include;
 pub mod label;
 pub mod preview;
+pub mod user;
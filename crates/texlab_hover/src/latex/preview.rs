@@ -5,6 +5,7 @@ use log::*;
 use std::io;
 use std::io::Cursor;
 use std::process::Stdio;
+use std::sync::Arc;
 use std::time::Duration;
 use tempfile::TempDir;
 use texlab_distro::*;
@@ -40,23 +41,6 @@ const PREVIEW_ENVIRONMENTS: &[&str] = &[
 
 const IGNORED_PACKAGES: &[&str] = &["biblatex", "pgf", "tikz"];
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-enum MathElement<'a> {
-    Environment(&'a LatexEnvironment),
-    Equation(&'a LatexEquation),
-    Inline(&'a LatexInline),
-}
-
-impl<'a> SyntaxNode for MathElement<'a> {
-    fn range(&self) -> Range {
-        match self {
-            MathElement::Environment(environment) => environment.range(),
-            MathElement::Equation(equation) => equation.range(),
-            MathElement::Inline(inline) => inline.range(),
-        }
-    }
-}
-
 #[derive(Debug)]
 enum RenderError {
     IO(io::Error),
@@ -64,6 +48,9 @@ enum RenderError {
     DviNotFound,
     DviPngNotInstalled,
     DviPngFaulty,
+    PdfNotFound,
+    PdfToPpmNotInstalled,
+    PdfToPpmFaulty,
     DecodeImage,
 }
 
@@ -111,24 +98,53 @@ impl LatexPreviewHoverProvider {
         names
     }
 
+    fn format(request: &FeatureRequest<TextDocumentPositionParams>) -> Format {
+        let document = request
+            .workspace()
+            .find_parent(&request.document().uri, &request.options)
+            .unwrap_or_else(|| Arc::clone(&request.view.document));
+
+        let program = match &document.tree {
+            SyntaxTree::Latex(tree) => tree.magic.tex_program.as_ref().map(String::as_str),
+            SyntaxTree::Bibtex(_) => None,
+        };
+
+        match program {
+            Some("xelatex") if request.distribution.supports_format(Format::Xelatex) => {
+                Format::Xelatex
+            }
+            Some("xelatex") | Some("lualatex") => Format::Lualatex,
+            _ => Format::Latex,
+        }
+    }
+
     async fn render(
         request: &FeatureRequest<TextDocumentPositionParams>,
         range: Range,
+        format: Format,
     ) -> Result<Hover, RenderError> {
-        let code = Self::generate_code(request, range);
+        let code = Self::generate_code(request, range, format);
         let params = CompileParams {
             file_name: "preview.tex",
             code: &code,
-            format: Format::Latex,
+            format,
             timeout: Duration::from_secs(10),
         };
         let directory = request.distribution.compile(params).await?.directory;
 
-        if !directory.path().join("preview.dvi").exists() {
-            return Err(RenderError::DviNotFound);
-        }
+        let image = if format == Format::Xelatex {
+            if !directory.path().join("preview.pdf").exists() {
+                return Err(RenderError::PdfNotFound);
+            }
+            Self::pdftoppm(&directory).await?
+        } else {
+            if !directory.path().join("preview.dvi").exists() {
+                return Err(RenderError::DviNotFound);
+            }
+            Self::dvipng(&directory).await?
+        };
 
-        let image = Self::add_margin(Self::dvipng(&directory).await?);
+        let image = Self::add_margin(image);
         let base64 = Self::encode_image(image);
         let markdown = format!("![preview](data:image/png;base64,{})", base64);
         directory.close()?;
@@ -141,9 +157,21 @@ impl LatexPreviewHoverProvider {
         })
     }
 
-    fn generate_code(request: &FeatureRequest<TextDocumentPositionParams>, range: Range) -> String {
+    fn generate_code(
+        request: &FeatureRequest<TextDocumentPositionParams>,
+        range: Range,
+        format: Format,
+    ) -> String {
         let mut code = String::new();
-        code.push_str("\\documentclass{article}\n");
+        match format {
+            Format::Lualatex => {
+                code.push_str("\\outputmode=0\n");
+                code.push_str("\\documentclass{article}\n");
+            }
+            // XeLaTeX cannot produce DVI, so the document class crops the page instead of dvipng.
+            Format::Xelatex => code.push_str("\\documentclass[varwidth]{standalone}\n"),
+            Format::Latex | Format::Pdflatex => code.push_str("\\documentclass{article}\n"),
+        }
         code.push_str("\\thispagestyle{empty}\n");
         Self::generate_includes(request, &mut code);
         Self::generate_command_definitions(request, &mut code);
@@ -196,6 +224,11 @@ impl LatexPreviewHoverProvider {
                 tree.command_definitions
                     .iter()
                     .map(|def| CharStream::extract(&document.text, def.range()))
+                    .chain(
+                        tree.primitive_definitions
+                            .iter()
+                            .map(|def| CharStream::extract(&document.text, def.range())),
+                    )
                     .for_each(|def| {
                         code.push_str(&def);
                         code.push('\n');
@@ -256,6 +289,22 @@ impl LatexPreviewHoverProvider {
         Ok(png)
     }
 
+    async fn pdftoppm(directory: &TempDir) -> Result<DynamicImage, RenderError> {
+        let process = Command::new("pdftoppm")
+            .args(&["-png", "-r", "175", "-singlefile", "preview.pdf", "preview"])
+            .current_dir(directory.path())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|_| RenderError::PdfToPpmNotInstalled)?;
+
+        process.await.map_err(|_| RenderError::PdfToPpmFaulty)?;
+
+        let png_file = directory.path().join("preview.png");
+        let png = image::open(png_file).map_err(|_| RenderError::DecodeImage)?;
+        Ok(png)
+    }
+
     fn add_margin(image: DynamicImage) -> DynamicImage {
         let margin = 5;
         let width = image.width() + 2 * margin;
@@ -297,40 +346,27 @@ impl FeatureProvider for LatexPreviewHoverProvider {
 
     #[boxed]
     async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let format = Self::format(request);
         if !request.client_capabilities.has_hover_markdown_support()
-            || !request.distribution.supports_format(Format::Latex)
-            || request.distribution.output_kind(Format::Latex) != OutputKind::Dvi
+            || !request.distribution.supports_format(format)
+            || (format == Format::Latex
+                && request.distribution.output_kind(format) != OutputKind::Dvi)
         {
             return None;
         }
 
         if let SyntaxTree::Latex(tree) = &request.document().tree {
-            let mut elements = Vec::new();
-            tree.math
-                .inlines
-                .iter()
-                .map(MathElement::Inline)
-                .for_each(|inline| elements.push(inline));
-
-            tree.math
-                .equations
-                .iter()
-                .map(MathElement::Equation)
-                .for_each(|eq| elements.push(eq));
-
-            tree.env
-                .environments
-                .iter()
-                .filter(|env| Self::is_math_environment(request, env))
-                .map(MathElement::Environment)
-                .for_each(|env| elements.push(env));
-
-            let range = elements
-                .iter()
-                .find(|elem| elem.range().contains(request.params.position))
-                .map(MathElement::range)?;
-
-            return match Self::render(request, range).await {
+            let position = request.params.position;
+            let range = tree.math_range(position).or_else(|| {
+                tree.env
+                    .environments
+                    .iter()
+                    .filter(|env| Self::is_math_environment(request, env))
+                    .map(LatexEnvironment::range)
+                    .find(|range| range.contains(position))
+            })?;
+
+            return match Self::render(request, range, format).await {
                 Ok(hover) => Some(hover),
                 Err(why) => {
                     let message = match why {
@@ -343,6 +379,9 @@ impl FeatureProvider for LatexPreviewHoverProvider {
                         RenderError::DviNotFound => "compilation failed".to_owned(),
                         RenderError::DviPngNotInstalled => "dvipng is not installed".to_owned(),
                         RenderError::DviPngFaulty => "dvipng failed".to_owned(),
+                        RenderError::PdfNotFound => "compilation failed".to_owned(),
+                        RenderError::PdfToPpmNotInstalled => "pdftoppm is not installed".to_owned(),
+                        RenderError::PdfToPpmFaulty => "pdftoppm failed".to_owned(),
                         RenderError::DecodeImage => "failed to decode image".to_owned(),
                     };
                     warn!("Preview failed: {}", message);
@@ -353,3 +392,37 @@ impl FeatureProvider for LatexPreviewHoverProvider {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(distribution: Box<dyn Distribution>) -> Format {
+        let spec = FeatureSpec {
+            files: vec![FeatureSpec::file(
+                "foo.tex",
+                "% !TEX program = xelatex\n\\documentclass{article}\n$x$",
+            )],
+            main_file: "foo.tex",
+            position: Position::new(2, 1),
+            distribution,
+            ..FeatureSpec::default()
+        };
+        let request: FeatureRequest<TextDocumentPositionParams> = spec.into();
+        LatexPreviewHoverProvider::format(&request)
+    }
+
+    #[test]
+    fn format_xelatex() {
+        let distribution = TestDistribution::default().with_formats(vec![Format::Xelatex]);
+        assert_eq!(format(Box::new(distribution)), Format::Xelatex);
+    }
+
+    #[test]
+    fn format_xelatex_unsupported() {
+        assert_eq!(
+            format(Box::new(TestDistribution::default())),
+            Format::Lualatex
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use futures_boxed::boxed;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexUserDefinitionHoverProvider;
+
+impl FeatureProvider for LatexUserDefinitionHoverProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Option<Hover>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<TextDocumentPositionParams>,
+    ) -> Option<Hover> {
+        let position = request.params.position;
+        if let SyntaxTree::Latex(tree) = &request.document().tree {
+            if let Some(command) = tree.find_command_by_name(position) {
+                let code = Self::find_command_definition(request, command.name.text())?;
+                return Some(Self::hover(code, command.name.range()));
+            }
+
+            let name = tree
+                .env
+                .environments
+                .iter()
+                .flat_map(|env| env.left.name().into_iter().chain(env.right.name()))
+                .find(|name| name.range().contains(position))?;
+            let code = Self::find_environment_definition(request, name.text())?;
+            return Some(Self::hover(code, name.range()));
+        }
+        None
+    }
+}
+
+impl LatexUserDefinitionHoverProvider {
+    fn find_command_definition(
+        request: &FeatureRequest<TextDocumentPositionParams>,
+        name: &str,
+    ) -> Option<String> {
+        for document in request.related_documents() {
+            if let SyntaxTree::Latex(tree) = &document.tree {
+                let range = tree
+                    .command_definitions
+                    .iter()
+                    .filter(|def| def.definition.name.text() == name)
+                    .map(SyntaxNode::range)
+                    .chain(
+                        tree.primitive_definitions
+                            .iter()
+                            .filter(|def| def.definition.name.text() == name)
+                            .map(SyntaxNode::range),
+                    )
+                    .next();
+
+                if let Some(range) = range {
+                    return Some(CharStream::extract(&document.text, range));
+                }
+            }
+        }
+        None
+    }
+
+    fn find_environment_definition(
+        request: &FeatureRequest<TextDocumentPositionParams>,
+        name: &str,
+    ) -> Option<String> {
+        for document in request.related_documents() {
+            if let SyntaxTree::Latex(tree) = &document.tree {
+                if let Some(definition) = tree
+                    .environment_definitions
+                    .iter()
+                    .find(|def| def.name().text() == name)
+                {
+                    return Some(CharStream::extract(&document.text, definition.range()));
+                }
+            }
+        }
+        None
+    }
+
+    fn hover(code: String, range: Range) -> Hover {
+        Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```latex\n{}\n```", code),
+            }),
+            range: Some(range),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command() {
+        let hover = test_feature(
+            LatexUserDefinitionHoverProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.tex", "\\def\\foo#1{#1}\n\\include{bar}"),
+                    FeatureSpec::file("bar.tex", "\\foo{x}"),
+                ],
+                main_file: "bar.tex",
+                position: Position::new(0, 2),
+                ..FeatureSpec::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            hover.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: "```latex\n\\def\\foo#1{#1}\n```".into(),
+            })
+        );
+        assert_eq!(hover.range, Some(Range::new_simple(0, 0, 0, 4)));
+    }
+
+    #[test]
+    fn environment() {
+        let hover = test_feature(
+            LatexUserDefinitionHoverProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\newenvironment{foo}{\\bfseries}{}\n\\begin{foo}\n\\end{foo}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 6),
+                ..FeatureSpec::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            hover.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: "```latex\n\\newenvironment{foo}{\\bfseries}{}\n```".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn undefined_command() {
+        let hover = test_feature(
+            LatexUserDefinitionHoverProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\foo")],
+                main_file: "foo.tex",
+                position: Position::new(0, 2),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(hover, None);
+    }
+}
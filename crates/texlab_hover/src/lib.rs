@@ -9,6 +9,7 @@ use self::latex::component::LatexComponentHoverProvider;
 use self::latex::include::LatexIncludeHoverProvider;
 use self::latex::label::LatexLabelHoverProvider;
 use self::latex::preview::LatexPreviewHoverProvider;
+use self::latex::user::LatexUserDefinitionHoverProvider;
 use futures_boxed::boxed;
 use texlab_protocol::{Hover, TextDocumentPositionParams};
 use texlab_workspace::*;
@@ -29,6 +30,7 @@ impl HoverProvider {
                 Box::new(LatexIncludeHoverProvider),
                 Box::new(LatexLabelHoverProvider),
                 Box::new(LatexPreviewHoverProvider),
+                Box::new(LatexUserDefinitionHoverProvider),
             ]),
         }
     }
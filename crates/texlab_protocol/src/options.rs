@@ -1,10 +1,50 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BibtexFormattingOptions {
     pub line_length: Option<i32>,
+    pub field_order: Option<Vec<String>>,
+    pub align_fields: Option<bool>,
+    pub lowercase_names: Option<bool>,
+    pub use_braces: Option<bool>,
+    pub trailing_commas: Option<bool>,
+    pub sort_entries: Option<BibtexSortOrder>,
+}
+
+impl BibtexFormattingOptions {
+    pub fn field_order(&self) -> &[String] {
+        self.field_order
+            .as_ref()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn align_fields(&self) -> bool {
+        self.align_fields.unwrap_or(false)
+    }
+
+    pub fn lowercase_names(&self) -> bool {
+        self.lowercase_names.unwrap_or(true)
+    }
+
+    pub fn use_braces(&self) -> bool {
+        self.use_braces.unwrap_or(false)
+    }
+
+    pub fn trailing_commas(&self) -> bool {
+        self.trailing_commas.unwrap_or(true)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BibtexSortOrder {
+    Key,
+    Year,
+    Author,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
@@ -37,6 +77,8 @@ pub struct LatexBuildOptions {
     pub args: Option<Vec<String>>,
     pub on_save: Option<bool>,
     pub output_directory: Option<PathBuf>,
+    pub aux_directory: Option<PathBuf>,
+    pub engine_args: Option<HashMap<String, Vec<String>>>,
 }
 
 impl LatexBuildOptions {
@@ -57,11 +99,30 @@ impl LatexBuildOptions {
         })
     }
 
+    pub fn engine_args(&self, program: &str) -> Option<Vec<String>> {
+        self.engine_args
+            .as_ref()
+            .and_then(|table| table.get(&program.to_lowercase()))
+            .cloned()
+    }
+
     pub fn on_save(&self) -> bool {
         self.on_save.unwrap_or(false)
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexCompletionOptions {
+    pub argument_snippets: Option<bool>,
+}
+
+impl LatexCompletionOptions {
+    pub fn argument_snippets(&self) -> bool {
+        self.argument_snippets.unwrap_or(false)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LatexOptions {
@@ -69,12 +130,25 @@ pub struct LatexOptions {
     pub lint: Option<LatexLintOptions>,
     pub build: Option<LatexBuildOptions>,
     pub root_directory: Option<PathBuf>,
+    pub completion: Option<LatexCompletionOptions>,
+    pub verbatim_environments: Option<Vec<String>>,
+    pub verbatim_commands: Option<Vec<String>>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BibtexOptions {
     pub formatting: Option<BibtexFormattingOptions>,
+    pub key_template: Option<String>,
+}
+
+impl BibtexOptions {
+    pub fn key_template(&self) -> String {
+        self.key_template
+            .as_ref()
+            .map(Clone::clone)
+            .unwrap_or_else(|| "{auth}{year}{shorttitle}".to_owned())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
@@ -84,15 +158,14 @@ pub struct Options {
     pub bibtex: Option<BibtexOptions>,
 }
 
+const AUX_EXTENSIONS: &[&str] = &["aux", "log", "fls", "fdb_latexmk", "bbl", "blg", "bcf"];
+
 impl Options {
     pub fn resolve_output_file(&self, tex_path: &Path, extension: &str) -> Option<PathBuf> {
         let stem = tex_path.file_stem()?.to_str()?;
         let name = format!("{}.{}", stem, extension);
 
-        self.latex
-            .as_ref()
-            .and_then(|latex| latex.build.as_ref())
-            .and_then(|build| build.output_directory.as_ref())
+        self.output_directory(extension)
             .map(|path| path.join(&name))
             .and_then(|path| dunce::canonicalize(path).ok())
             .or_else(|| {
@@ -103,16 +176,44 @@ impl Options {
                     .and_then(|path| dunce::canonicalize(path).ok())
             })
             .or_else(|| {
-                self.latex
-                    .as_ref()
-                    .and_then(|latex| latex.build.as_ref())
-                    .and_then(|build| build.output_directory.as_ref())
-                    .and_then(|path| {
-                        tex_path
-                            .parent()
-                            .map(|parent| parent.join(path).join(&name))
-                    })
+                self.output_directory(extension).and_then(|path| {
+                    tex_path
+                        .parent()
+                        .map(|parent| parent.join(path).join(&name))
+                })
             })
             .or_else(|| tex_path.parent().map(|path| path.join(&name)))
     }
+
+    fn output_directory(&self, extension: &str) -> Option<&PathBuf> {
+        let build = self.latex.as_ref().and_then(|latex| latex.build.as_ref())?;
+        if AUX_EXTENSIONS.contains(&extension) {
+            build
+                .aux_directory
+                .as_ref()
+                .or_else(|| build.output_directory.as_ref())
+        } else {
+            build.output_directory.as_ref()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn engine_args_custom() {
+        let mut table = HashMap::new();
+        table.insert("lualatex".to_owned(), vec!["-lualatex".to_owned()]);
+        let options = LatexBuildOptions {
+            engine_args: Some(table),
+            ..LatexBuildOptions::default()
+        };
+        assert_eq!(
+            options.engine_args("LuaLaTeX"),
+            Some(vec!["-lualatex".to_owned()])
+        );
+        assert_eq!(options.engine_args("pdflatex"), None);
+    }
 }
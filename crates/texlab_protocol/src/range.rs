@@ -7,6 +7,8 @@ pub trait RangeExt {
     fn contains(&self, position: Position) -> bool;
 
     fn contains_exclusive(&self, position: Position) -> bool;
+
+    fn intersects(&self, other: Range) -> bool;
 }
 
 impl RangeExt for Range {
@@ -29,6 +31,10 @@ impl RangeExt for Range {
     fn contains_exclusive(&self, position: Position) -> bool {
         position > self.start && position < self.end
     }
+
+    fn intersects(&self, other: Range) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
 }
 
 #[cfg(test)]
@@ -94,4 +100,22 @@ mod tests {
         let range = Range::new_simple(1, 2, 3, 4);
         assert!(!range.contains_exclusive(Position::new(5, 1)));
     }
+
+    #[test]
+    fn intersects_overlap() {
+        let range = Range::new_simple(1, 2, 3, 4);
+        assert!(range.intersects(Range::new_simple(3, 0, 5, 0)));
+    }
+
+    #[test]
+    fn intersects_inside() {
+        let range = Range::new_simple(1, 2, 3, 4);
+        assert!(range.intersects(Range::new_simple(2, 0, 2, 0)));
+    }
+
+    #[test]
+    fn intersects_outside() {
+        let range = Range::new_simple(1, 2, 3, 4);
+        assert!(!range.intersects(Range::new_simple(3, 5, 4, 0)));
+    }
 }
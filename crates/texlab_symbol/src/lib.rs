@@ -188,7 +188,7 @@ pub async fn workspace_symbols<'a>(
     let provider = SymbolProvider::new();
     let mut symbols = Vec::new();
 
-    for document in &workspace.documents {
+    for document in workspace.documents() {
         let uri: Uri = document.uri.clone();
         let request = FeatureRequest {
             client_capabilities: Arc::clone(&client_capabilities),
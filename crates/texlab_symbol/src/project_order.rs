@@ -14,7 +14,7 @@ pub struct ProjectOrdering {
 impl ProjectOrdering {
     pub fn new(workspace: &Workspace, options: &Options) -> Self {
         let mut ordering = Vec::new();
-        let connected_components = Self::connected_components(workspace, options);
+        let connected_components = workspace.components(options);
         for connected_component in connected_components {
             let graph = Self::build_dependency_graph(&connected_component);
 
@@ -45,23 +45,6 @@ impl ProjectOrdering {
         Self { ordering }
     }
 
-    fn connected_components(workspace: &Workspace, options: &Options) -> Vec<Vec<Arc<Document>>> {
-        let mut components = Vec::new();
-        let mut visited = HashSet::new();
-        for root in &workspace.documents {
-            if !visited.insert(root.uri.clone()) {
-                continue;
-            }
-
-            let component = workspace.related_documents(&root.uri, options);
-            for document in &component {
-                visited.insert(document.uri.clone());
-            }
-            components.push(component);
-        }
-        components
-    }
-
     fn build_dependency_graph(documents: &[Arc<Document>]) -> Graph<usize, (), Directed> {
         let mut graph = Graph::new();
         let nodes: Vec<_> = (0..documents.len()).map(|i| graph.add_node(i)).collect();
@@ -1,7 +1,8 @@
 use crate::text::{Span, SyntaxNode};
+use serde::{Deserialize, Serialize};
 use texlab_protocol::{Range, RangeExt};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum BibtexTokenKind {
     PreambleKind,
     StringKind,
@@ -18,7 +19,7 @@ pub enum BibtexTokenKind {
     EndParen,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexToken {
     pub span: Span,
     pub kind: BibtexTokenKind,
@@ -40,7 +41,7 @@ impl SyntaxNode for BibtexToken {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexRoot {
     pub children: Vec<BibtexDeclaration>,
 }
@@ -64,7 +65,7 @@ impl SyntaxNode for BibtexRoot {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum BibtexDeclaration {
     Comment(Box<BibtexComment>),
     Preamble(Box<BibtexPreamble>),
@@ -94,7 +95,7 @@ impl SyntaxNode for BibtexDeclaration {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexComment {
     pub range: Range,
     pub token: BibtexToken,
@@ -115,7 +116,7 @@ impl SyntaxNode for BibtexComment {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexPreamble {
     pub range: Range,
     pub ty: BibtexToken,
@@ -156,7 +157,7 @@ impl SyntaxNode for BibtexPreamble {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexString {
     pub range: Range,
     pub ty: BibtexToken,
@@ -208,7 +209,7 @@ impl SyntaxNode for BibtexString {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexEntry {
     pub range: Range,
     pub ty: BibtexToken,
@@ -270,7 +271,7 @@ impl SyntaxNode for BibtexEntry {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexField {
     pub range: Range,
     pub name: BibtexToken,
@@ -312,7 +313,7 @@ impl SyntaxNode for BibtexField {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum BibtexContent {
     Word(BibtexWord),
     Command(BibtexCommand),
@@ -331,6 +332,63 @@ impl BibtexContent {
             BibtexContent::Concat(concat) => visitor.visit_concat(concat),
         }
     }
+
+    pub fn text(&self) -> String {
+        let mut tokens = Vec::new();
+        self.collect_tokens(&mut tokens);
+
+        let mut text = String::new();
+        let mut insert_space = false;
+        for i in 0..tokens.len() {
+            let (token, is_delimiter) = tokens[i];
+            if i > 0 {
+                let previous = tokens[i - 1].0;
+                insert_space |= previous.end().line != token.start().line
+                    || previous.end().character < token.start().character;
+            }
+
+            if !is_delimiter {
+                if insert_space && !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(token.text());
+                insert_space = false;
+            }
+        }
+        text
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<(&'a BibtexToken, bool)>) {
+        match self {
+            BibtexContent::Word(word) => tokens.push((&word.token, false)),
+            BibtexContent::Command(command) => tokens.push((&command.token, false)),
+            BibtexContent::QuotedContent(content) => {
+                tokens.push((&content.left, true));
+                for child in &content.children {
+                    child.collect_tokens(tokens);
+                }
+                if let Some(right) = &content.right {
+                    tokens.push((right, true));
+                }
+            }
+            BibtexContent::BracedContent(content) => {
+                tokens.push((&content.left, true));
+                for child in &content.children {
+                    child.collect_tokens(tokens);
+                }
+                if let Some(right) = &content.right {
+                    tokens.push((right, true));
+                }
+            }
+            BibtexContent::Concat(concat) => {
+                concat.left.collect_tokens(tokens);
+                tokens.push((&concat.operator, true));
+                if let Some(right) = &concat.right {
+                    right.collect_tokens(tokens);
+                }
+            }
+        }
+    }
 }
 
 impl SyntaxNode for BibtexContent {
@@ -345,7 +403,7 @@ impl SyntaxNode for BibtexContent {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexWord {
     pub range: Range,
     pub token: BibtexToken,
@@ -366,7 +424,7 @@ impl SyntaxNode for BibtexWord {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexCommand {
     pub range: Range,
     pub token: BibtexToken,
@@ -387,7 +445,7 @@ impl SyntaxNode for BibtexCommand {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexQuotedContent {
     pub range: Range,
     pub left: BibtexToken,
@@ -424,7 +482,7 @@ impl SyntaxNode for BibtexQuotedContent {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexBracedContent {
     pub range: Range,
     pub left: BibtexToken,
@@ -461,7 +519,7 @@ impl SyntaxNode for BibtexBracedContent {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexConcat {
     pub range: Range,
     pub left: BibtexContent,
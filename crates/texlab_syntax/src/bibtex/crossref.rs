@@ -0,0 +1,173 @@
+use super::ast::*;
+use super::BibtexSyntaxTree;
+use std::collections::{HashSet, VecDeque};
+
+pub const BIBTEX_CROSSREF_FIELDS: &[&str] = &["crossref", "xref", "xdata"];
+
+const BIBTEX_INHERITING_FIELDS: &[&str] = &["crossref", "xdata"];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BibtexInheritedField<'a> {
+    pub field: &'a BibtexField,
+    pub parent: &'a BibtexEntry,
+}
+
+impl BibtexField {
+    pub fn is_crossref(&self) -> bool {
+        BIBTEX_CROSSREF_FIELDS.contains(&self.name.text().to_lowercase().as_str())
+    }
+
+    pub fn crossref_keys(&self) -> Vec<&BibtexToken> {
+        if !self.is_crossref() {
+            return Vec::new();
+        }
+
+        let children: Vec<&BibtexContent> = match &self.content {
+            Some(BibtexContent::BracedContent(content)) => content.children.iter().collect(),
+            Some(BibtexContent::QuotedContent(content)) => content.children.iter().collect(),
+            Some(content @ BibtexContent::Word(_)) => vec![content],
+            _ => Vec::new(),
+        };
+
+        children
+            .into_iter()
+            .filter_map(|child| match child {
+                BibtexContent::Word(word) if word.token.text() != "," => Some(&word.token),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+pub fn find_entry<'a>(trees: &[&'a BibtexSyntaxTree], key: &str) -> Option<&'a BibtexEntry> {
+    trees
+        .iter()
+        .flat_map(|tree| tree.entries())
+        .filter(|entry| !entry.is_comment())
+        .find(|entry| entry.key.as_ref().map(BibtexToken::text) == Some(key))
+}
+
+pub fn inherited_fields<'a>(
+    trees: &[&'a BibtexSyntaxTree],
+    entry: &'a BibtexEntry,
+) -> Vec<BibtexInheritedField<'a>> {
+    let mut names: HashSet<String> = entry
+        .fields
+        .iter()
+        .map(|field| field.name.text().to_lowercase())
+        .collect();
+
+    let mut visited = HashSet::new();
+    if let Some(key) = &entry.key {
+        visited.insert(key.text());
+    }
+
+    let mut fields = Vec::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(entry);
+    while let Some(child) = queue.pop_front() {
+        for field in &child.fields {
+            let name = field.name.text().to_lowercase();
+            if !BIBTEX_INHERITING_FIELDS.contains(&name.as_str()) {
+                continue;
+            }
+
+            for key in field.crossref_keys() {
+                if !visited.insert(key.text()) {
+                    continue;
+                }
+
+                if let Some(parent) = find_entry(trees, key.text()) {
+                    for parent_field in &parent.fields {
+                        if !parent_field.is_crossref()
+                            && names.insert(parent_field.name.text().to_lowercase())
+                        {
+                            fields.push(BibtexInheritedField {
+                                field: parent_field,
+                                parent,
+                            });
+                        }
+                    }
+                    queue.push_back(parent);
+                }
+            }
+        }
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inherited_names(text: &str) -> Vec<(String, String)> {
+        let tree = BibtexSyntaxTree::from(text);
+        let entry = tree.entries()[0];
+        inherited_fields(&[&tree], entry)
+            .into_iter()
+            .map(|inherited| {
+                (
+                    inherited.field.name.text().to_owned(),
+                    inherited.parent.key.as_ref().unwrap().text().to_owned(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn crossref_keys_braced() {
+        let tree = BibtexSyntaxTree::from("@article{foo, xdata = {bar, baz}}");
+        let keys: Vec<&str> = tree.entries()[0].fields[0]
+            .crossref_keys()
+            .into_iter()
+            .map(BibtexToken::text)
+            .collect();
+        assert_eq!(keys, vec!["bar", "baz"]);
+    }
+
+    #[test]
+    fn crossref_keys_other_field() {
+        let tree = BibtexSyntaxTree::from("@article{foo, title = {bar}}");
+        assert!(tree.entries()[0].fields[0].crossref_keys().is_empty());
+    }
+
+    #[test]
+    fn inherited_crossref() {
+        let names = inherited_names(
+            "@inproceedings{foo, crossref = {bar}, title = {Foo}}\n\
+             @proceedings{bar, title = {Bar}, year = {2020}}",
+        );
+        assert_eq!(names, vec![("year".into(), "bar".into())]);
+    }
+
+    #[test]
+    fn inherited_nested() {
+        let names = inherited_names(
+            "@inproceedings{foo, crossref = {bar}}\n\
+             @proceedings{bar, xdata = {baz}, year = {2020}}\n\
+             @xdata{baz, publisher = {Qux}}",
+        );
+        assert_eq!(
+            names,
+            vec![
+                ("year".into(), "bar".into()),
+                ("publisher".into(), "baz".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn inherited_xref() {
+        let names =
+            inherited_names("@inproceedings{foo, xref = {bar}}\n@proceedings{bar, year = {2020}}");
+        assert_eq!(names, Vec::new());
+    }
+
+    #[test]
+    fn inherited_cycle() {
+        let names = inherited_names(
+            "@article{foo, crossref = {bar}}\n@article{bar, crossref = {foo}, year = {2020}}",
+        );
+        assert_eq!(names, vec![("year".into(), "bar".into())]);
+    }
+}
@@ -1,6 +1,7 @@
 use super::ast::*;
-use crate::text::SyntaxNode;
-use texlab_protocol::BibtexFormattingOptions;
+use super::BibtexSyntaxTree;
+use crate::text::{CharStream, SyntaxNode};
+use texlab_protocol::{BibtexFormattingOptions, BibtexSortOrder, Position, Range};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BibtexFormattingParams {
@@ -18,6 +19,14 @@ impl BibtexFormattingParams {
             line_length
         }
     }
+
+    pub fn indent(&self) -> String {
+        if self.insert_spaces {
+            " ".repeat(self.tab_size)
+        } else {
+            "\t".into()
+        }
+    }
 }
 
 impl Default for BibtexFormattingParams {
@@ -38,19 +47,9 @@ struct BibtexFormatter<'a> {
 
 impl<'a> BibtexFormatter<'a> {
     fn new(params: &'a BibtexFormattingParams) -> Self {
-        let indent = if params.insert_spaces {
-            let mut buffer = String::new();
-            for _ in 0..params.tab_size {
-                buffer.push(' ');
-            }
-            buffer
-        } else {
-            "\t".into()
-        };
-
         Self {
             params,
-            indent,
+            indent: params.indent(),
             output: String::new(),
         }
     }
@@ -88,37 +87,67 @@ impl<'a> BibtexFormatter<'a> {
             self.output.push_str(key.text());
             self.output.push(',');
             self.output.push('\n');
-            for field in &entry.fields {
-                self.format_field(field);
+
+            let fields = self.sort_fields(&entry.fields);
+            let width = if self.params.options.align_fields() {
+                fields
+                    .iter()
+                    .map(|field| field.name.text().chars().count())
+                    .max()
+                    .unwrap_or(0)
+            } else {
+                0
+            };
+
+            for (i, field) in fields.iter().enumerate() {
+                let is_last = i == fields.len() - 1;
+                self.format_field(field, width, is_last);
             }
             self.output.push('}');
         }
     }
 
-    fn format_field(&mut self, field: &BibtexField) {
+    fn sort_fields<'b>(&self, fields: &'b [BibtexField]) -> Vec<&'b BibtexField> {
+        let order = self.params.options.field_order();
+        let mut fields: Vec<_> = fields.iter().collect();
+        fields.sort_by_key(|field| {
+            order
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(field.name.text()))
+                .unwrap_or_else(|| order.len())
+        });
+        fields
+    }
+
+    fn format_field(&mut self, field: &BibtexField, width: usize, is_last: bool) {
         self.output.push_str(self.indent.as_ref());
         self.format_token(&field.name);
-        self.output.push_str(" = ");
         let count = field.name.text().chars().count();
-        let align = self.params.tab_size as usize + count + 3;
+        for _ in count..width {
+            self.output.push(' ');
+        }
+        self.output.push_str(" = ");
+        let align = self.params.tab_size as usize + count.max(width) + 3;
         if let Some(ref content) = field.content {
             self.format_content(content, align);
-            self.output.push(',');
+            if !is_last || self.params.options.trailing_commas() {
+                self.output.push(',');
+            }
             self.output.push('\n');
         }
     }
 
     fn format_content(&mut self, content: &BibtexContent, align: usize) {
-        let mut analyzer = BibtexContentAnalyzer::new();
+        let mut analyzer = BibtexContentAnalyzer::new(self.params.options.use_braces());
         content.accept(&mut analyzer);
         let tokens = analyzer.tokens;
-        self.output.push_str(tokens[0].text());
+        self.output.push_str(tokens[0].1);
 
-        let mut length = align + tokens[0].text().chars().count();
+        let mut length = align + tokens[0].1.chars().count();
         for i in 1..tokens.len() {
-            let previous = tokens[i - 1];
-            let current = tokens[i];
-            let current_length = current.text().chars().count();
+            let (previous, _) = tokens[i - 1];
+            let (current, current_text) = tokens[i];
+            let current_length = current_text.chars().count();
 
             let insert_space = Self::should_insert_space(previous, current);
             let space_length = if insert_space { 1 } else { 0 };
@@ -134,13 +163,17 @@ impl<'a> BibtexFormatter<'a> {
                 self.output.push(' ');
                 length += 1;
             }
-            self.output.push_str(current.text());
+            self.output.push_str(current_text);
             length += current_length;
         }
     }
 
     fn format_token(&mut self, token: &BibtexToken) {
-        self.output.push_str(token.text().to_lowercase().as_ref());
+        if self.params.options.lowercase_names() {
+            self.output.push_str(token.text().to_lowercase().as_ref());
+        } else {
+            self.output.push_str(token.text());
+        }
     }
 
     fn should_insert_space(previous: &BibtexToken, current: &BibtexToken) -> bool {
@@ -150,12 +183,20 @@ impl<'a> BibtexFormatter<'a> {
 }
 
 struct BibtexContentAnalyzer<'a> {
-    pub tokens: Vec<&'a BibtexToken>,
+    use_braces: bool,
+    pub tokens: Vec<(&'a BibtexToken, &'a str)>,
 }
 
 impl<'a> BibtexContentAnalyzer<'a> {
-    pub fn new() -> Self {
-        Self { tokens: Vec::new() }
+    pub fn new(use_braces: bool) -> Self {
+        Self {
+            use_braces,
+            tokens: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, token: &'a BibtexToken) {
+        self.tokens.push((token, token.text()));
     }
 }
 
@@ -173,32 +214,42 @@ impl<'a> BibtexVisitor<'a> for BibtexContentAnalyzer<'a> {
     fn visit_field(&mut self, _field: &'a BibtexField) {}
 
     fn visit_word(&mut self, word: &'a BibtexWord) {
-        self.tokens.push(&word.token);
+        self.push(&word.token);
     }
 
     fn visit_command(&mut self, command: &'a BibtexCommand) {
-        self.tokens.push(&command.token);
+        self.push(&command.token);
     }
 
     fn visit_quoted_content(&mut self, content: &'a BibtexQuotedContent) {
-        self.tokens.push(&content.left);
+        let use_braces = self.use_braces && content.right.is_some();
+        if use_braces {
+            self.tokens.push((&content.left, "{"));
+        } else {
+            self.push(&content.left);
+        }
+
         BibtexWalker::walk_quoted_content(self, content);
         if let Some(ref right) = content.right {
-            self.tokens.push(right);
+            if use_braces {
+                self.tokens.push((right, "}"));
+            } else {
+                self.push(right);
+            }
         }
     }
 
     fn visit_braced_content(&mut self, content: &'a BibtexBracedContent) {
-        self.tokens.push(&content.left);
+        self.push(&content.left);
         BibtexWalker::walk_braced_content(self, content);
         if let Some(ref right) = content.right {
-            self.tokens.push(right);
+            self.push(right);
         }
     }
 
     fn visit_concat(&mut self, concat: &'a BibtexConcat) {
         concat.left.accept(self);
-        self.tokens.push(&concat.operator);
+        self.push(&concat.operator);
         if let Some(ref right) = concat.right {
             right.accept(self);
         }
@@ -247,6 +298,105 @@ pub fn format_content(content: &BibtexContent, params: &BibtexFormattingParams)
     formatter.output
 }
 
+#[derive(Debug)]
+struct BibtexBlock<'a> {
+    comment: String,
+    declaration: &'a BibtexDeclaration,
+}
+
+pub fn format_bibliography(
+    text: &str,
+    tree: &BibtexSyntaxTree,
+    params: &BibtexFormattingParams,
+) -> String {
+    let mut blocks = Vec::new();
+    let mut comment_start = Position::new(0, 0);
+    for declaration in &tree.root.children {
+        let is_comment = match declaration {
+            BibtexDeclaration::Comment(_) => true,
+            BibtexDeclaration::Preamble(_) | BibtexDeclaration::String(_) => false,
+            BibtexDeclaration::Entry(entry) => entry.is_comment(),
+        };
+
+        if !is_comment {
+            let range = Range::new(comment_start, declaration.start());
+            blocks.push(BibtexBlock {
+                comment: CharStream::extract(text, range).trim().to_owned(),
+                declaration,
+            });
+            comment_start = declaration.end();
+        }
+    }
+
+    if let Some(order) = params.options.sort_entries {
+        blocks.sort_by_key(|block| match block.declaration {
+            BibtexDeclaration::Entry(entry) => Some(entry_sort_key(entry, order)),
+            _ => None,
+        });
+    }
+
+    let range = Range::new(comment_start, CharStream::end_position(text));
+    let trailing_comment = CharStream::extract(text, range).trim().to_owned();
+
+    let mut output = Vec::new();
+    for block in blocks {
+        let mut block_text = block.comment;
+        if !block_text.is_empty() {
+            block_text.push('\n');
+        }
+        block_text.push_str(&format_declaration(block.declaration, params));
+        output.push(block_text);
+    }
+
+    if !trailing_comment.is_empty() {
+        output.push(trailing_comment);
+    }
+
+    let mut output = output.join("\n\n");
+    output.push('\n');
+    output
+}
+
+fn entry_sort_key(entry: &BibtexEntry, order: BibtexSortOrder) -> (bool, String, String) {
+    let field_text = |name: &str| {
+        entry
+            .field(name)
+            .and_then(|field| field.content.as_ref())
+            .map(BibtexContent::text)
+    };
+
+    let year = field_text("year")
+        .or_else(|| field_text("date"))
+        .map(|date| date.chars().take(4).collect::<String>());
+
+    let (primary, secondary) = match order {
+        BibtexSortOrder::Key => (
+            entry.key.as_ref().map(|key| key.text().to_lowercase()),
+            None,
+        ),
+        BibtexSortOrder::Year => (year, None),
+        BibtexSortOrder::Author => {
+            let author = field_text("author")
+                .or_else(|| field_text("editor"))
+                .and_then(|names| {
+                    let name = names.split(" and ").next()?.trim().to_owned();
+                    match name.find(',') {
+                        Some(index) => Some(name[..index].to_owned()),
+                        None => name.split_whitespace().last().map(ToOwned::to_owned),
+                    }
+                })
+                .map(|name| name.to_lowercase());
+            (author, year)
+        }
+    };
+
+    (
+        primary.is_none(),
+        primary.unwrap_or_default(),
+        secondary.unwrap_or_default(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,6 +410,7 @@ mod tests {
             insert_spaces: true,
             options: BibtexFormattingOptions {
                 line_length: Some(line_length),
+                ..BibtexFormattingOptions::default()
             },
         };
         assert_eq!(
@@ -268,6 +419,29 @@ mod tests {
         );
     }
 
+    fn verify_with_options(source: &str, expected: &str, options: BibtexFormattingOptions) {
+        let tree = BibtexSyntaxTree::from(source);
+        let params = BibtexFormattingParams {
+            tab_size: 4,
+            insert_spaces: true,
+            options,
+        };
+        assert_eq!(
+            expected,
+            format_declaration(&tree.root.children[0], &params)
+        );
+    }
+
+    fn verify_bibliography(source: &str, expected: &str, options: BibtexFormattingOptions) {
+        let tree = BibtexSyntaxTree::from(source);
+        let params = BibtexFormattingParams {
+            tab_size: 4,
+            insert_spaces: true,
+            options,
+        };
+        assert_eq!(expected, format_bibliography(source, &tree, &params));
+    }
+
     #[test]
     fn wrap_long_lines() {
         let source =
@@ -368,4 +542,188 @@ mod tests {
         let expected = "@preamble{\"foo bar baz\"}";
         verify(source, expected, 30);
     }
+
+    #[test]
+    fn field_order() {
+        let source = "@article{foo, year = 2020, title = bar, author = baz}";
+        let expected = indoc!(
+            "
+            @article{foo,
+                author = baz,
+                title = bar,
+                year = 2020,
+            }"
+        );
+        let options = BibtexFormattingOptions {
+            field_order: Some(vec!["author".into(), "title".into()]),
+            ..BibtexFormattingOptions::default()
+        };
+        verify_with_options(source, expected, options);
+    }
+
+    #[test]
+    fn align_fields() {
+        let source = "@article{foo, title = bar, year = 2020}";
+        let expected = indoc!(
+            "
+            @article{foo,
+                title = bar,
+                year  = 2020,
+            }"
+        );
+        let options = BibtexFormattingOptions {
+            align_fields: Some(true),
+            ..BibtexFormattingOptions::default()
+        };
+        verify_with_options(source, expected, options);
+    }
+
+    #[test]
+    fn keep_case() {
+        let source = "@Article{foo, Title = bar}";
+        let expected = indoc!(
+            "
+            @Article{foo,
+                Title = bar,
+            }"
+        );
+        let options = BibtexFormattingOptions {
+            lowercase_names: Some(false),
+            ..BibtexFormattingOptions::default()
+        };
+        verify_with_options(source, expected, options);
+    }
+
+    #[test]
+    fn use_braces() {
+        let source = "@article{foo, title = \"bar {Baz}\" # qux}";
+        let expected = indoc!(
+            "
+            @article{foo,
+                title = {bar {Baz}} # qux,
+            }"
+        );
+        let options = BibtexFormattingOptions {
+            use_braces: Some(true),
+            ..BibtexFormattingOptions::default()
+        };
+        verify_with_options(source, expected, options);
+    }
+
+    #[test]
+    fn no_trailing_commas() {
+        let source = "@article{foo, title = bar, year = 2020,}";
+        let expected = indoc!(
+            "
+            @article{foo,
+                title = bar,
+                year = 2020
+            }"
+        );
+        let options = BibtexFormattingOptions {
+            trailing_commas: Some(false),
+            ..BibtexFormattingOptions::default()
+        };
+        verify_with_options(source, expected, options);
+    }
+
+    #[test]
+    fn bibliography_keep_comments() {
+        let source = indoc!(
+            "
+            % foo
+            @article{foo, year = 2020}
+            @comment{bar = baz}
+
+            @string{qux = \"Qux\"}
+            trailing text
+            "
+        );
+        let expected = indoc!(
+            "
+            % foo
+            @article{foo,
+                year = 2020,
+            }
+
+            @comment{bar = baz}
+            @string{qux = \"Qux\"}
+
+            trailing text
+            "
+        );
+        verify_bibliography(source, expected, BibtexFormattingOptions::default());
+    }
+
+    #[test]
+    fn bibliography_sort_by_key() {
+        let source = "@string{foo = bar}\n@article{b,}\n% a\n@article{a,}\n";
+        let expected = indoc!(
+            "
+            @string{foo = bar}
+
+            % a
+            @article{a,
+            }
+
+            @article{b,
+            }
+            "
+        );
+        let options = BibtexFormattingOptions {
+            sort_entries: Some(BibtexSortOrder::Key),
+            ..BibtexFormattingOptions::default()
+        };
+        verify_bibliography(source, expected, options);
+    }
+
+    #[test]
+    fn bibliography_sort_by_year() {
+        let source = "@article{a, year = 2020}\n@article{b}\n@article{c, date = {2019-01-01}}";
+        let expected = indoc!(
+            "
+            @article{c,
+                date = {2019-01-01},
+            }
+
+            @article{a,
+                year = 2020,
+            }
+
+            @article{b,
+            }
+            "
+        );
+        let options = BibtexFormattingOptions {
+            sort_entries: Some(BibtexSortOrder::Year),
+            ..BibtexFormattingOptions::default()
+        };
+        verify_bibliography(source, expected, options);
+    }
+
+    #[test]
+    fn bibliography_sort_by_author() {
+        let source = indoc!(
+            "
+            @article{a, author = {Zeta, Foo}}
+            @article{b, author = {Bar Alpha and Baz Qux}}
+            "
+        );
+        let expected = indoc!(
+            "
+            @article{b,
+                author = {Bar Alpha and Baz Qux},
+            }
+
+            @article{a,
+                author = {Zeta, Foo},
+            }
+            "
+        );
+        let options = BibtexFormattingOptions {
+            sort_entries: Some(BibtexSortOrder::Author),
+            ..BibtexFormattingOptions::default()
+        };
+        verify_bibliography(source, expected, options);
+    }
 }
@@ -1,18 +1,25 @@
 mod ast;
+mod crossref;
 mod finder;
 mod formatting;
 mod lexer;
+mod name_list;
 mod parser;
+mod unicode;
 
 pub use self::ast::*;
+pub use self::crossref::*;
 pub use self::finder::*;
 pub use self::formatting::*;
+pub use self::name_list::*;
+pub use self::unicode::*;
 
 use self::lexer::BibtexLexer;
 use self::parser::BibtexParser;
+use serde::{Deserialize, Serialize};
 use texlab_protocol::Position;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct BibtexSyntaxTree {
     pub root: BibtexRoot,
 }
@@ -62,12 +69,8 @@ impl BibtexSyntaxTree {
 
     pub fn crossref(&self, entry: &BibtexEntry) -> Option<&BibtexEntry> {
         let field = entry.field("crossref")?;
-        if let Some(BibtexContent::BracedContent(content)) = &field.content {
-            if let Some(BibtexContent::Word(name)) = content.children.get(0) {
-                return self.entry(name.token.text());
-            }
-        }
-        None
+        let key = field.crossref_keys().into_iter().next()?;
+        self.entry(key.text())
     }
 }
 
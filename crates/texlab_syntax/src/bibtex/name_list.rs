@@ -0,0 +1,83 @@
+use super::ast::*;
+use crate::text::SyntaxNode;
+use texlab_protocol::Range;
+
+pub const BIBTEX_NAME_FIELDS: &[&str] = &["author", "editor"];
+
+const NAME_SUFFIXES: &[&str] = &["jr", "jr.", "sr", "sr.", "ii", "iii", "iv"];
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct BibtexName<'a> {
+    pub parts: Vec<&'a BibtexContent>,
+}
+
+impl<'a> BibtexName<'a> {
+    pub fn range(&self) -> Option<Range> {
+        let first = self.parts.first()?;
+        let last = self.parts.last()?;
+        Some(Range::new(first.start(), last.end()))
+    }
+
+    pub fn segments(&self) -> Vec<BibtexName<'a>> {
+        let mut segments = vec![BibtexName::default()];
+        for part in &self.parts {
+            if word_text(part) == Some(",") {
+                segments.push(BibtexName::default());
+            } else {
+                segments.last_mut().unwrap().parts.push(*part);
+            }
+        }
+        segments
+    }
+
+    pub fn is_comma_separated_list(&self) -> bool {
+        let segments = self.segments();
+        match segments.len() {
+            0..=2 => false,
+            3 => {
+                let suffix = &segments[1].parts;
+                suffix.len() != 1
+                    || !word_text(suffix[0])
+                        .map(|text| NAME_SUFFIXES.contains(&text.to_lowercase().as_str()))
+                        .unwrap_or(false)
+            }
+            _ => true,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BibtexNameList<'a> {
+    pub names: Vec<BibtexName<'a>>,
+    pub separators: Vec<&'a BibtexToken>,
+}
+
+impl<'a> BibtexNameList<'a> {
+    pub fn parse(content: &'a BibtexContent) -> Option<Self> {
+        let children = match content {
+            BibtexContent::BracedContent(content) => &content.children,
+            BibtexContent::QuotedContent(content) => &content.children,
+            _ => return None,
+        };
+
+        let mut names = vec![BibtexName::default()];
+        let mut separators = Vec::new();
+        for child in children {
+            match child {
+                BibtexContent::Word(word) if word.token.text().eq_ignore_ascii_case("and") => {
+                    separators.push(&word.token);
+                    names.push(BibtexName::default());
+                }
+                _ => names.last_mut().unwrap().parts.push(child),
+            }
+        }
+        Some(Self { names, separators })
+    }
+}
+
+fn word_text(content: &BibtexContent) -> Option<&str> {
+    match content {
+        BibtexContent::Word(word) => Some(word.token.text()),
+        _ => None,
+    }
+}
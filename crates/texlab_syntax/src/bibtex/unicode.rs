@@ -0,0 +1,314 @@
+use super::ast::*;
+use crate::text::{CharStream, SyntaxNode};
+use texlab_protocol::{Position, Range};
+use unicode_normalization::UnicodeNormalization;
+
+const ACCENTS: &[(&str, char)] = &[
+    ("\\`", '\u{0300}'),
+    ("\\'", '\u{0301}'),
+    ("\\^", '\u{0302}'),
+    ("\\~", '\u{0303}'),
+    ("\\=", '\u{0304}'),
+    ("\\u", '\u{0306}'),
+    ("\\.", '\u{0307}'),
+    ("\\\"", '\u{0308}'),
+    ("\\r", '\u{030A}'),
+    ("\\H", '\u{030B}'),
+    ("\\v", '\u{030C}'),
+    ("\\d", '\u{0323}'),
+    ("\\c", '\u{0327}'),
+    ("\\k", '\u{0328}'),
+    ("\\b", '\u{0331}'),
+];
+
+const SYMBOLS: &[(&str, char)] = &[
+    ("\\ss", 'ß'),
+    ("\\ae", 'æ'),
+    ("\\AE", 'Æ'),
+    ("\\oe", 'œ'),
+    ("\\OE", 'Œ'),
+    ("\\o", 'ø'),
+    ("\\O", 'Ø'),
+    ("\\aa", 'å'),
+    ("\\AA", 'Å'),
+    ("\\l", 'ł'),
+    ("\\L", 'Ł'),
+    ("\\i", 'ı'),
+    ("\\j", 'ȷ'),
+];
+
+type Edit = (Range, String);
+
+pub fn content_to_unicode(text: &str, content: &BibtexContent) -> String {
+    let mut edits = Vec::new();
+    collect_unicode_edits(content, &mut edits);
+    apply_edits(text, content.range(), edits)
+}
+
+pub fn content_to_latex(text: &str, content: &BibtexContent) -> String {
+    let mut edits = Vec::new();
+    collect_latex_edits(content, &mut edits);
+    apply_edits(text, content.range(), edits)
+}
+
+pub fn protect_title(text: &str, content: &BibtexContent) -> String {
+    let children = match content {
+        BibtexContent::BracedContent(content) => &content.children,
+        BibtexContent::QuotedContent(content) => &content.children,
+        _ => return CharStream::extract(text, content.range()),
+    };
+
+    let mut edits = Vec::new();
+    let mut is_first_word = true;
+    for child in children {
+        if let BibtexContent::Word(word) = child {
+            let word_text = word.token.text();
+            if word_text.chars().any(char::is_alphabetic) {
+                let is_capitalized = word_text.chars().next().map_or(false, char::is_uppercase);
+                let has_inner_capital = word_text.chars().skip(1).any(char::is_uppercase);
+                if has_inner_capital || (is_capitalized && !is_first_word) {
+                    edits.push((word.range(), format!("{{{}}}", word_text)));
+                }
+                is_first_word = false;
+            }
+        } else {
+            is_first_word = false;
+        }
+    }
+    apply_edits(text, content.range(), edits)
+}
+
+fn apply_edits(text: &str, range: Range, edits: Vec<Edit>) -> String {
+    let mut output = String::new();
+    let mut position = range.start;
+    for (edit_range, new_text) in edits {
+        output.push_str(&CharStream::extract(
+            text,
+            Range::new(position, edit_range.start),
+        ));
+        output.push_str(&new_text);
+        position = edit_range.end;
+    }
+    output.push_str(&CharStream::extract(text, Range::new(position, range.end)));
+    output
+}
+
+fn collect_unicode_edits(content: &BibtexContent, edits: &mut Vec<Edit>) {
+    match content {
+        BibtexContent::BracedContent(braced) => {
+            if braced.right.is_some() {
+                if let Some((c, consumed, _)) = parse_accent(&braced.children, 0) {
+                    if consumed == braced.children.len() {
+                        edits.push((braced.range, c.to_string()));
+                        return;
+                    }
+                }
+            }
+            collect_unicode_children(&braced.children, edits);
+        }
+        BibtexContent::QuotedContent(quoted) => collect_unicode_children(&quoted.children, edits),
+        BibtexContent::Concat(concat) => {
+            collect_unicode_edits(&concat.left, edits);
+            if let Some(right) = &concat.right {
+                collect_unicode_edits(right, edits);
+            }
+        }
+        BibtexContent::Word(_) | BibtexContent::Command(_) => {}
+    }
+}
+
+fn collect_unicode_children(children: &[BibtexContent], edits: &mut Vec<Edit>) {
+    let mut index = 0;
+    while index < children.len() {
+        if let Some((c, consumed, end)) = parse_accent(children, index) {
+            edits.push((Range::new(children[index].start(), end), c.to_string()));
+            index += consumed;
+        } else {
+            collect_unicode_edits(&children[index], edits);
+            index += 1;
+        }
+    }
+}
+
+fn parse_accent(children: &[BibtexContent], index: usize) -> Option<(char, usize, Position)> {
+    let command = match children.get(index)? {
+        BibtexContent::Command(command) => command,
+        _ => return None,
+    };
+    let name = command.token.text();
+
+    if let Some((_, symbol)) = SYMBOLS.iter().find(|(symbol, _)| *symbol == name) {
+        return match children.get(index + 1) {
+            Some(BibtexContent::BracedContent(braced))
+                if braced.children.is_empty() && braced.right.is_some() =>
+            {
+                Some((*symbol, 2, braced.end()))
+            }
+            _ => Some((*symbol, 1, command.end())),
+        };
+    }
+
+    let (_, mark) = ACCENTS.iter().find(|(accent, _)| *accent == name)?;
+    let (base, end) = match children.get(index + 1)? {
+        BibtexContent::Word(word) => {
+            let base = word.token.text().chars().next()?;
+            let start = word.start();
+            (base, Position::new(start.line, start.character + 1))
+        }
+        BibtexContent::Command(command) => (dotless_base(command)?, command.end()),
+        BibtexContent::BracedContent(braced) if braced.right.is_some() => {
+            match braced.children.as_slice() {
+                [BibtexContent::Word(word)] if word.token.text().chars().count() == 1 => {
+                    (word.token.text().chars().next()?, braced.end())
+                }
+                [BibtexContent::Command(command)] => (dotless_base(command)?, braced.end()),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    let composed: Vec<char> = format!("{}{}", base, mark).nfc().collect();
+    match composed.as_slice() {
+        [c] => Some((*c, 2, end)),
+        _ => None,
+    }
+}
+
+fn dotless_base(command: &BibtexCommand) -> Option<char> {
+    match command.token.text() {
+        "\\i" => Some('i'),
+        "\\j" => Some('j'),
+        _ => None,
+    }
+}
+
+fn collect_latex_edits(content: &BibtexContent, edits: &mut Vec<Edit>) {
+    match content {
+        BibtexContent::BracedContent(braced) => {
+            for child in &braced.children {
+                collect_latex_edits(child, edits);
+            }
+        }
+        BibtexContent::QuotedContent(quoted) => {
+            for child in &quoted.children {
+                collect_latex_edits(child, edits);
+            }
+        }
+        BibtexContent::Concat(concat) => {
+            collect_latex_edits(&concat.left, edits);
+            if let Some(right) = &concat.right {
+                collect_latex_edits(right, edits);
+            }
+        }
+        BibtexContent::Word(word) => {
+            let text = word.token.text();
+            if !text.is_ascii() {
+                edits.push((word.range(), text.chars().map(char_to_latex).collect()));
+            }
+        }
+        BibtexContent::Command(_) => {}
+    }
+}
+
+fn char_to_latex(c: char) -> String {
+    if c.is_ascii() {
+        return c.to_string();
+    }
+
+    if let Some((symbol, _)) = SYMBOLS.iter().find(|(_, symbol)| *symbol == c) {
+        return format!("{{{}}}", symbol);
+    }
+
+    let mut chars = std::iter::once(c).nfd();
+    let base = chars.next().unwrap();
+    if !base.is_ascii() {
+        return c.to_string();
+    }
+
+    let mut output = base.to_string();
+    for mark in chars {
+        let accent = match ACCENTS.iter().find(|(_, accent_mark)| *accent_mark == mark) {
+            Some((accent, _)) => accent,
+            None => return c.to_string(),
+        };
+
+        let is_letter = accent.chars().nth(1).map_or(false, char::is_alphabetic);
+        output = if output.chars().count() > 1 {
+            format!("{}{{{}}}", accent, output)
+        } else if is_letter {
+            format!("{} {}", accent, output)
+        } else {
+            format!("{}{}", accent, output)
+        };
+    }
+    format!("{{{}}}", output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bibtex::BibtexSyntaxTree;
+
+    fn verify<F: Fn(&str, &BibtexContent) -> String>(convert: F, field: &str, expected: &str) {
+        let text = format!("@article{{foo, title = {}}}", field);
+        let tree = BibtexSyntaxTree::from(text.as_str());
+        let content = tree.entries()[0].fields[0].content.as_ref().unwrap();
+        assert_eq!(convert(&text, content), expected);
+    }
+
+    #[test]
+    fn unicode_braced_group() {
+        verify(
+            content_to_unicode,
+            "{M{\\\"u}ller and Ca{\\~n}{\\'e}}",
+            "{Müller and Cañé}",
+        );
+    }
+
+    #[test]
+    fn unicode_command_argument() {
+        verify(
+            content_to_unicode,
+            "\"\\\"{o}\\\"uber \\v{s} \\v c {\\'\\i}\"",
+            "\"öüber š č í\"",
+        );
+    }
+
+    #[test]
+    fn unicode_symbols() {
+        verify(
+            content_to_unicode,
+            "{Stra{\\ss}e \\o{} \\aa}",
+            "{Straße ø å}",
+        );
+    }
+
+    #[test]
+    fn unicode_unknown() {
+        verify(content_to_unicode, "{\\\"{xy} \\foo}", "{\\\"{xy} \\foo}");
+    }
+
+    #[test]
+    fn latex() {
+        verify(
+            content_to_latex,
+            "{Müller, Straße and Dvořák \\\"o}",
+            "{M{\\\"u}ller, Stra{\\ss}e and Dvo{\\v r}{\\'a}k \\\"o}",
+        );
+    }
+
+    #[test]
+    fn latex_unknown() {
+        verify(content_to_latex, "{日本}", "{日本}");
+    }
+
+    #[test]
+    fn protect() {
+        verify(
+            protect_title,
+            "{The DNA of {RNA} in Europe and iPhones}",
+            "{The {DNA} of {RNA} in {Europe} and {iPhones}}",
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use super::language::LANGUAGE_DATA;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyValCommand {
+    pub name: String,
+    pub index: usize,
+    pub optional: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyValValueKind {
+    Text,
+    Boolean,
+    Color,
+    Choice,
+}
+
+impl Default for KeyValValueKind {
+    fn default() -> Self {
+        KeyValValueKind::Text
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyValKey {
+    pub name: String,
+    #[serde(default)]
+    pub kind: KeyValValueKind,
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+impl KeyValKey {
+    pub fn value_names(&self) -> Vec<&str> {
+        match self.kind {
+            KeyValValueKind::Text => Vec::new(),
+            KeyValValueKind::Boolean => vec!["true", "false"],
+            KeyValValueKind::Color => LANGUAGE_DATA.colors.iter().map(AsRef::as_ref).collect(),
+            KeyValValueKind::Choice => self.values.iter().map(AsRef::as_ref).collect(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyValInterface {
+    pub name: String,
+    pub commands: Vec<KeyValCommand>,
+    #[serde(default)]
+    pub environments: Vec<String>,
+    #[serde(default)]
+    pub packages: Vec<String>,
+    pub keys: Vec<KeyValKey>,
+}
+
+impl KeyValInterface {
+    pub fn find_key(&self, name: &str) -> Option<&KeyValKey> {
+        self.keys.iter().find(|key| key.name == name)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyValData {
+    pub interfaces: Vec<KeyValInterface>,
+}
+
+pub static KEYVAL_DATA: Lazy<KeyValData> = Lazy::new(|| {
+    const JSON: &str = include_str!("keyval.json");
+    serde_json::from_str(JSON).expect("Failed to deserialize keyval.json")
+});
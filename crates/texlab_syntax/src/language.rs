@@ -81,6 +81,17 @@ impl LatexIncludeKind {
             LatexIncludeKind::Everything => None,
         }
     }
+
+    pub fn is_graphics(self) -> bool {
+        match self {
+            LatexIncludeKind::Image | LatexIncludeKind::Svg | LatexIncludeKind::Pdf => true,
+            LatexIncludeKind::Package
+            | LatexIncludeKind::Class
+            | LatexIncludeKind::Latex
+            | LatexIncludeKind::Bibliography
+            | LatexIncludeKind::Everything => false,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -97,10 +108,40 @@ pub struct LatexIncludeCommand {
 pub struct LatexCommandDefinitionCommand {
     pub name: String,
     pub definition_index: usize,
+    #[serde(default)]
     pub argument_count_index: usize,
+    #[serde(default)]
+    pub argument_spec_index: Option<usize>,
     pub implementation_index: usize,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexEnvironmentDefinitionCommand {
+    pub name: String,
+    pub name_index: usize,
+    #[serde(default)]
+    pub argument_count_index: usize,
+    #[serde(default)]
+    pub argument_spec_index: Option<usize>,
+    pub begin_index: usize,
+    pub end_index: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LatexPrimitiveDefinitionKind {
+    Def,
+    Let,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexPrimitiveDefinitionCommand {
+    pub name: String,
+    pub kind: LatexPrimitiveDefinitionKind,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LatexMathOperatorCommand {
@@ -153,6 +194,32 @@ pub struct LatexGlossaryEntryReferenceCommand {
     pub kind: LatexGlossaryEntryKind,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexVerbatimCommand {
+    pub name: String,
+    #[serde(default)]
+    pub index: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LatexRegisterKind {
+    Counter,
+    Length,
+    Box,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexRegisterCommand {
+    pub name: String,
+    pub index: usize,
+    pub kind: LatexRegisterKind,
+    #[serde(default)]
+    pub definition: bool,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum BibtexEntryTypeCategory {
@@ -171,6 +238,8 @@ pub struct BibtexEntryTypeDoc {
     pub name: String,
     pub category: BibtexEntryTypeCategory,
     pub documentation: Option<String>,
+    #[serde(default)]
+    pub required_fields: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -189,6 +258,8 @@ pub struct LanguageData {
     pub section_commands: Vec<LatexSectionCommand>,
     pub include_commands: Vec<LatexIncludeCommand>,
     pub command_definition_commands: Vec<LatexCommandDefinitionCommand>,
+    pub environment_definition_commands: Vec<LatexEnvironmentDefinitionCommand>,
+    pub primitive_definition_commands: Vec<LatexPrimitiveDefinitionCommand>,
     pub math_operator_commands: Vec<LatexMathOperatorCommand>,
     pub theorem_definition_commands: Vec<LatexTheoremDefinitionCommand>,
     pub colors: Vec<String>,
@@ -196,12 +267,18 @@ pub struct LanguageData {
     pub color_model_commands: Vec<LatexColorModelCommand>,
     pub glossary_entry_definition_commands: Vec<LatexGlossaryEntryDefinitionCommand>,
     pub glossary_entry_reference_commands: Vec<LatexGlossaryEntryReferenceCommand>,
+    pub register_commands: Vec<LatexRegisterCommand>,
     pub entry_types: Vec<BibtexEntryTypeDoc>,
     pub fields: Vec<BibtexFieldDoc>,
     pub pgf_libraries: Vec<String>,
     pub tikz_libraries: Vec<String>,
     pub math_environments: Vec<String>,
+    pub math_text_commands: Vec<String>,
+    pub math_commands: Vec<String>,
+    pub text_commands: Vec<String>,
     pub enum_environments: Vec<String>,
+    pub verbatim_environments: Vec<String>,
+    pub verbatim_commands: Vec<LatexVerbatimCommand>,
 }
 
 impl LanguageData {
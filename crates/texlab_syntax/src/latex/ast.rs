@@ -1,9 +1,10 @@
 use crate::text::{Span, SyntaxNode};
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use texlab_protocol::{Range, RangeExt};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum LatexTokenKind {
     Word,
     Command,
@@ -15,7 +16,7 @@ pub enum LatexTokenKind {
     EndOptions,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexToken {
     pub span: Span,
     pub kind: LatexTokenKind,
@@ -37,7 +38,7 @@ impl SyntaxNode for LatexToken {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 pub struct LatexRoot {
     pub children: Vec<LatexContent>,
 }
@@ -61,7 +62,7 @@ impl SyntaxNode for LatexRoot {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum LatexContent {
     Group(Arc<LatexGroup>),
     Command(Arc<LatexCommand>),
@@ -94,13 +95,13 @@ impl SyntaxNode for LatexContent {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum LatexGroupKind {
     Group,
     Options,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexGroup {
     pub range: Range,
     pub left: LatexToken,
@@ -140,7 +141,7 @@ impl SyntaxNode for LatexGroup {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexCommand {
     pub range: Range,
     pub name: LatexToken,
@@ -246,7 +247,7 @@ impl SyntaxNode for LatexCommand {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexText {
     pub range: Range,
     pub words: Vec<LatexToken>,
@@ -267,7 +268,7 @@ impl SyntaxNode for LatexText {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexComma {
     pub token: LatexToken,
 }
@@ -284,7 +285,7 @@ impl SyntaxNode for LatexComma {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexMath {
     pub token: LatexToken,
 }
@@ -1,10 +1,11 @@
 use super::ast::*;
 use crate::language::*;
 use crate::text::SyntaxNode;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use texlab_protocol::Range;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexEnvironmentDelimiter {
     pub command: Arc<LatexCommand>,
 }
@@ -36,7 +37,7 @@ impl SyntaxNode for LatexEnvironmentDelimiter {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexEnvironment {
     pub left: LatexEnvironmentDelimiter,
     pub right: LatexEnvironmentDelimiter,
@@ -98,7 +99,7 @@ impl SyntaxNode for LatexEnvironment {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexEnvironmentInfo {
     pub environments: Vec<LatexEnvironment>,
     pub is_standalone: bool,
@@ -1,10 +1,11 @@
 use super::ast::*;
 use crate::language::*;
 use crate::text::SyntaxNode;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use texlab_protocol::Range;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexGlossaryEntry {
     pub command: Arc<LatexCommand>,
     pub label_index: usize,
@@ -44,7 +45,7 @@ impl LatexGlossaryEntry {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexGlossaryInfo {
     pub entries: Vec<LatexGlossaryEntry>,
 }
@@ -1,14 +1,44 @@
 use super::ast::{LatexToken, LatexTokenKind};
+use crate::language::{LatexVerbatimCommand, LANGUAGE_DATA};
 use crate::text::CharStream;
+use texlab_protocol::Options;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum LatexVerbatimState {
+    Normal,
+    Begin,
+    BeginGroup,
+    Name(String),
+    Environment { name: String, depth: usize },
+    Command { depth: usize, skip: usize },
+}
 
 pub struct LatexLexer<'a> {
     stream: CharStream<'a>,
+    verbatim_environments: Vec<String>,
+    verbatim_commands: Vec<LatexVerbatimCommand>,
+    state: LatexVerbatimState,
 }
 
 impl<'a> LatexLexer<'a> {
-    pub fn new(text: &'a str) -> Self {
+    pub fn new(text: &'a str, options: &Options) -> Self {
+        let mut verbatim_environments = LANGUAGE_DATA.verbatim_environments.clone();
+        let mut verbatim_commands = LANGUAGE_DATA.verbatim_commands.clone();
+        if let Some(options) = &options.latex {
+            verbatim_environments.extend(options.verbatim_environments.iter().flatten().cloned());
+            verbatim_commands.extend(options.verbatim_commands.iter().flatten().map(|name| {
+                LatexVerbatimCommand {
+                    name: name.clone(),
+                    index: 0,
+                }
+            }));
+        }
+
         Self {
             stream: CharStream::new(text),
+            verbatim_environments,
+            verbatim_commands,
+            state: LatexVerbatimState::Normal,
         }
     }
 
@@ -44,12 +74,8 @@ impl<'a> LatexLexer<'a> {
         let span = self.stream.end_span();
         LatexToken::new(span, LatexTokenKind::Word)
     }
-}
 
-impl<'a> Iterator for LatexLexer<'a> {
-    type Item = LatexToken;
-
-    fn next(&mut self) -> Option<LatexToken> {
+    fn token(&mut self) -> Option<LatexToken> {
         loop {
             match self.stream.peek() {
                 Some('%') => {
@@ -89,6 +115,165 @@ impl<'a> Iterator for LatexLexer<'a> {
             }
         }
     }
+
+    fn verbatim(&mut self, length: usize) -> Option<LatexToken> {
+        if length == 0 {
+            return None;
+        }
+
+        let end = self.stream.current_index + length;
+        self.stream.start_span();
+        while self.stream.current_index < end {
+            self.stream.next();
+        }
+        let span = self.stream.end_span();
+        Some(LatexToken::new(span, LatexTokenKind::Word))
+    }
+
+    fn verbatim_environment(&mut self, name: &str) -> Option<LatexToken> {
+        while self.stream.satifies(|c| c.is_whitespace()) {
+            self.stream.next();
+        }
+
+        let rest = self.stream.rest();
+        let end = format!("\\end{{{}}}", name);
+        let length = rest.find(&end).unwrap_or(rest.len());
+        self.verbatim(rest[..length].trim_end().len())
+    }
+
+    fn verbatim_command(&mut self) -> Option<LatexToken> {
+        let mut chars = self.stream.rest().char_indices();
+        let (_, left) = chars.next()?;
+        if left.is_whitespace() {
+            return None;
+        }
+
+        let mut depth = 1;
+        let length = chars
+            .take_while(|(_, c)| *c != '\n')
+            .find(|(_, c)| {
+                if left != '{' {
+                    return *c == left;
+                }
+
+                match c {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => (),
+                }
+                depth == 0
+            })
+            .map(|(i, c)| i + c.len_utf8())?;
+        self.verbatim(length)
+    }
+
+    fn update_state(&mut self, token: &LatexToken) {
+        let state = std::mem::replace(&mut self.state, LatexVerbatimState::Normal);
+        let verbatim_index = self
+            .verbatim_commands
+            .iter()
+            .find(|cmd| cmd.name == token.text())
+            .map(|cmd| cmd.index);
+        self.state = match (state, token.kind) {
+            (_, LatexTokenKind::Command) if token.text() == "\\begin" => LatexVerbatimState::Begin,
+            (_, LatexTokenKind::Command) if verbatim_index.is_some() => {
+                LatexVerbatimState::Command {
+                    depth: 0,
+                    skip: verbatim_index.unwrap(),
+                }
+            }
+            (LatexVerbatimState::Begin, LatexTokenKind::BeginGroup) => {
+                LatexVerbatimState::BeginGroup
+            }
+            (LatexVerbatimState::BeginGroup, LatexTokenKind::Word)
+                if self
+                    .verbatim_environments
+                    .iter()
+                    .any(|env| env == token.text()) =>
+            {
+                LatexVerbatimState::Name(token.text().to_owned())
+            }
+            (LatexVerbatimState::Name(name), LatexTokenKind::EndGroup) => {
+                LatexVerbatimState::Environment { name, depth: 0 }
+            }
+            (LatexVerbatimState::Environment { name, depth }, kind) => match kind {
+                LatexTokenKind::BeginGroup | LatexTokenKind::BeginOptions => {
+                    LatexVerbatimState::Environment {
+                        name,
+                        depth: depth + 1,
+                    }
+                }
+                LatexTokenKind::EndGroup | LatexTokenKind::EndOptions if depth > 0 => {
+                    LatexVerbatimState::Environment {
+                        name,
+                        depth: depth - 1,
+                    }
+                }
+                _ if depth > 0 => LatexVerbatimState::Environment { name, depth },
+                _ => LatexVerbatimState::Normal,
+            },
+            (LatexVerbatimState::Command { depth, skip }, kind) => match kind {
+                LatexTokenKind::BeginOptions => LatexVerbatimState::Command {
+                    depth: depth + 1,
+                    skip,
+                },
+                LatexTokenKind::BeginGroup if depth > 0 => LatexVerbatimState::Command {
+                    depth: depth + 1,
+                    skip,
+                },
+                LatexTokenKind::BeginGroup if skip > 0 => LatexVerbatimState::Command {
+                    depth: depth + 1,
+                    skip: skip - 1,
+                },
+                LatexTokenKind::EndGroup | LatexTokenKind::EndOptions if depth > 0 => {
+                    LatexVerbatimState::Command {
+                        depth: depth - 1,
+                        skip,
+                    }
+                }
+                _ if depth > 0 => LatexVerbatimState::Command { depth, skip },
+                _ => LatexVerbatimState::Normal,
+            },
+            _ => LatexVerbatimState::Normal,
+        };
+    }
+}
+
+impl<'a> Iterator for LatexLexer<'a> {
+    type Item = LatexToken;
+
+    fn next(&mut self) -> Option<LatexToken> {
+        match self.state.clone() {
+            LatexVerbatimState::Environment { name, depth: 0 } => {
+                while self.stream.satifies(|c| *c == ' ' || *c == '\t') {
+                    self.stream.next();
+                }
+
+                if !self.stream.satifies(|c| *c == '[' || *c == '{') {
+                    self.state = LatexVerbatimState::Normal;
+                    if let Some(token) = self.verbatim_environment(&name) {
+                        return Some(token);
+                    }
+                }
+            }
+            LatexVerbatimState::Command { depth: 0, skip } => {
+                if !self
+                    .stream
+                    .satifies(|c| *c == '[' || (skip > 0 && *c == '{'))
+                {
+                    self.state = LatexVerbatimState::Normal;
+                    if let Some(token) = self.verbatim_command() {
+                        return Some(token);
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        let token = self.token()?;
+        self.update_state(&token);
+        Some(token)
+    }
 }
 
 fn is_word_char(c: char) -> bool {
@@ -107,7 +292,7 @@ fn is_word_char(c: char) -> bool {
 mod tests {
     use super::*;
     use crate::text::Span;
-    use texlab_protocol::{Position, Range};
+    use texlab_protocol::{LatexOptions, Position, Range};
 
     fn verify<'a>(
         lexer: &mut LatexLexer<'a>,
@@ -126,7 +311,7 @@ mod tests {
 
     #[test]
     fn word() {
-        let mut lexer = LatexLexer::new("foo bar baz");
+        let mut lexer = LatexLexer::new("foo bar baz", &Options::default());
         verify(&mut lexer, 0, 0, "foo", LatexTokenKind::Word);
         verify(&mut lexer, 0, 4, "bar", LatexTokenKind::Word);
         verify(&mut lexer, 0, 8, "baz", LatexTokenKind::Word);
@@ -135,7 +320,7 @@ mod tests {
 
     #[test]
     fn command() {
-        let mut lexer = LatexLexer::new("\\foo\\bar@baz\n\\foo*");
+        let mut lexer = LatexLexer::new("\\foo\\bar@baz\n\\foo*", &Options::default());
         verify(&mut lexer, 0, 0, "\\foo", LatexTokenKind::Command);
         verify(&mut lexer, 0, 4, "\\bar@baz", LatexTokenKind::Command);
         verify(&mut lexer, 1, 0, "\\foo*", LatexTokenKind::Command);
@@ -144,7 +329,7 @@ mod tests {
 
     #[test]
     fn escape_sequence() {
-        let mut lexer = LatexLexer::new("\\%\\**");
+        let mut lexer = LatexLexer::new("\\%\\**", &Options::default());
         verify(&mut lexer, 0, 0, "\\%", LatexTokenKind::Command);
         verify(&mut lexer, 0, 2, "\\*", LatexTokenKind::Command);
         verify(&mut lexer, 0, 4, "*", LatexTokenKind::Word);
@@ -153,7 +338,7 @@ mod tests {
 
     #[test]
     fn group_delimiter() {
-        let mut lexer = LatexLexer::new("{}[]");
+        let mut lexer = LatexLexer::new("{}[]", &Options::default());
         verify(&mut lexer, 0, 0, "{", LatexTokenKind::BeginGroup);
         verify(&mut lexer, 0, 1, "}", LatexTokenKind::EndGroup);
         verify(&mut lexer, 0, 2, "[", LatexTokenKind::BeginOptions);
@@ -163,7 +348,7 @@ mod tests {
 
     #[test]
     fn math() {
-        let mut lexer = LatexLexer::new("$$ $ $");
+        let mut lexer = LatexLexer::new("$$ $ $", &Options::default());
         verify(&mut lexer, 0, 0, "$$", LatexTokenKind::Math);
         verify(&mut lexer, 0, 3, "$", LatexTokenKind::Math);
         verify(&mut lexer, 0, 5, "$", LatexTokenKind::Math);
@@ -172,8 +357,113 @@ mod tests {
 
     #[test]
     fn line_comment() {
-        let mut lexer = LatexLexer::new(" %foo \nfoo");
+        let mut lexer = LatexLexer::new(" %foo \nfoo", &Options::default());
         verify(&mut lexer, 1, 0, "foo", LatexTokenKind::Word);
         assert_eq!(None, lexer.next());
     }
+
+    #[test]
+    fn verbatim_environment() {
+        let mut lexer = LatexLexer::new(
+            "\\begin{verbatim}\n\\foo{\n\\end{verbatim}",
+            &Options::default(),
+        );
+        verify(&mut lexer, 0, 0, "\\begin", LatexTokenKind::Command);
+        verify(&mut lexer, 0, 6, "{", LatexTokenKind::BeginGroup);
+        verify(&mut lexer, 0, 7, "verbatim", LatexTokenKind::Word);
+        verify(&mut lexer, 0, 15, "}", LatexTokenKind::EndGroup);
+        verify(&mut lexer, 1, 0, "\\foo{", LatexTokenKind::Word);
+        verify(&mut lexer, 2, 0, "\\end", LatexTokenKind::Command);
+        verify(&mut lexer, 2, 4, "{", LatexTokenKind::BeginGroup);
+        verify(&mut lexer, 2, 5, "verbatim", LatexTokenKind::Word);
+        verify(&mut lexer, 2, 13, "}", LatexTokenKind::EndGroup);
+        assert_eq!(None, lexer.next());
+    }
+
+    #[test]
+    fn verbatim_environment_arguments() {
+        let mut lexer = LatexLexer::new(
+            "\\begin{minted}{python}\nx = {\n\\end{minted}",
+            &Options::default(),
+        );
+        verify(&mut lexer, 0, 0, "\\begin", LatexTokenKind::Command);
+        verify(&mut lexer, 0, 6, "{", LatexTokenKind::BeginGroup);
+        verify(&mut lexer, 0, 7, "minted", LatexTokenKind::Word);
+        verify(&mut lexer, 0, 13, "}", LatexTokenKind::EndGroup);
+        verify(&mut lexer, 0, 14, "{", LatexTokenKind::BeginGroup);
+        verify(&mut lexer, 0, 15, "python", LatexTokenKind::Word);
+        verify(&mut lexer, 0, 21, "}", LatexTokenKind::EndGroup);
+        verify(&mut lexer, 1, 0, "x = {", LatexTokenKind::Word);
+        verify(&mut lexer, 2, 0, "\\end", LatexTokenKind::Command);
+    }
+
+    #[test]
+    fn verbatim_environment_options() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                verbatim_environments: Some(vec!["code".into()]),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        };
+        let mut lexer = LatexLexer::new("\\begin{code}\\foo\\end{code}", &options);
+        verify(&mut lexer, 0, 0, "\\begin", LatexTokenKind::Command);
+        verify(&mut lexer, 0, 6, "{", LatexTokenKind::BeginGroup);
+        verify(&mut lexer, 0, 7, "code", LatexTokenKind::Word);
+        verify(&mut lexer, 0, 11, "}", LatexTokenKind::EndGroup);
+        verify(&mut lexer, 0, 12, "\\foo", LatexTokenKind::Word);
+        verify(&mut lexer, 0, 16, "\\end", LatexTokenKind::Command);
+    }
+
+    #[test]
+    fn verbatim_command() {
+        let mut lexer = LatexLexer::new(
+            "\\verb|\\foo{|bar \\lstinline[language=C]{x}",
+            &Options::default(),
+        );
+        verify(&mut lexer, 0, 0, "\\verb", LatexTokenKind::Command);
+        verify(&mut lexer, 0, 5, "|\\foo{|", LatexTokenKind::Word);
+        verify(&mut lexer, 0, 12, "bar", LatexTokenKind::Word);
+        verify(&mut lexer, 0, 16, "\\lstinline", LatexTokenKind::Command);
+        verify(&mut lexer, 0, 26, "[", LatexTokenKind::BeginOptions);
+        verify(&mut lexer, 0, 27, "language=C", LatexTokenKind::Word);
+        verify(&mut lexer, 0, 37, "]", LatexTokenKind::EndOptions);
+        verify(&mut lexer, 0, 38, "{x}", LatexTokenKind::Word);
+        assert_eq!(None, lexer.next());
+    }
+
+    #[test]
+    fn verbatim_command_nested_braces() {
+        let mut lexer = LatexLexer::new("\\lstinline{a{b}c} d", &Options::default());
+        verify(&mut lexer, 0, 0, "\\lstinline", LatexTokenKind::Command);
+        verify(&mut lexer, 0, 10, "{a{b}c}", LatexTokenKind::Word);
+        verify(&mut lexer, 0, 18, "d", LatexTokenKind::Word);
+        assert_eq!(None, lexer.next());
+    }
+
+    #[test]
+    fn verbatim_command_arguments() {
+        let mut lexer = LatexLexer::new("\\mintinline{python}{x = \\foo}", &Options::default());
+        verify(&mut lexer, 0, 0, "\\mintinline", LatexTokenKind::Command);
+        verify(&mut lexer, 0, 11, "{", LatexTokenKind::BeginGroup);
+        verify(&mut lexer, 0, 12, "python", LatexTokenKind::Word);
+        verify(&mut lexer, 0, 18, "}", LatexTokenKind::EndGroup);
+        verify(&mut lexer, 0, 19, "{x = \\foo}", LatexTokenKind::Word);
+        assert_eq!(None, lexer.next());
+    }
+
+    #[test]
+    fn verbatim_command_custom() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                verbatim_commands: Some(vec!["\\code".into()]),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        };
+        let mut lexer = LatexLexer::new("\\code{\\foo}", &options);
+        verify(&mut lexer, 0, 0, "\\code", LatexTokenKind::Command);
+        verify(&mut lexer, 0, 5, "{\\foo}", LatexTokenKind::Word);
+        assert_eq!(None, lexer.next());
+    }
 }
@@ -0,0 +1,185 @@
+use crate::SyntaxTreeInput;
+use path_clean::PathClean;
+use serde::{Deserialize, Serialize};
+use texlab_protocol::Uri;
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct LatexMagicComment {
+    pub line: u64,
+    pub program: String,
+    pub key: String,
+    pub value: String,
+}
+
+impl LatexMagicComment {
+    fn parse(line: u64, text: &str) -> Option<Self> {
+        let text = text.trim_start();
+        if !text.starts_with('%') {
+            return None;
+        }
+
+        let text = text[1..].trim_start();
+        if !text.starts_with('!') {
+            return None;
+        }
+
+        let text = &text[1..];
+        let equals = text.find('=')?;
+        let mut words = text[..equals].split_whitespace();
+        let program = words.next()?.to_uppercase();
+        let key = words.next()?.to_lowercase();
+        if words.next().is_some() {
+            return None;
+        }
+
+        let value = text[equals + 1..].trim().to_owned();
+        if value.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            line,
+            program,
+            key,
+            value,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+pub struct LatexMagicCommentInfo {
+    pub comments: Vec<LatexMagicComment>,
+    pub root: Option<Uri>,
+    pub tex_program: Option<String>,
+    pub bib_program: Option<String>,
+}
+
+impl LatexMagicCommentInfo {
+    pub fn parse(input: SyntaxTreeInput) -> Self {
+        let comments: Vec<_> = input
+            .text
+            .lines()
+            .enumerate()
+            .filter_map(|(line, text)| LatexMagicComment::parse(line as u64, text))
+            .collect();
+
+        let root = Self::find(&comments, "TEX", "root")
+            .and_then(|comment| Self::resolve_root(input.uri, &comment.value));
+
+        let tex_program = Self::find(&comments, "TEX", "program").map(Self::program_name);
+        let bib_program = Self::find(&comments, "BIB", "program").map(Self::program_name);
+
+        Self {
+            comments,
+            root,
+            tex_program,
+            bib_program,
+        }
+    }
+
+    fn find<'a>(
+        comments: &'a [LatexMagicComment],
+        program: &str,
+        key: &str,
+    ) -> Option<&'a LatexMagicComment> {
+        comments
+            .iter()
+            .find(|comment| comment.program == program && comment.key == key)
+    }
+
+    fn program_name(comment: &LatexMagicComment) -> String {
+        comment.value.to_lowercase()
+    }
+
+    fn resolve_root(uri: &Uri, value: &str) -> Option<Uri> {
+        let mut path = uri.to_file_path().ok()?;
+        path.pop();
+        path.push(value.replace('\\', "/"));
+        if path.extension().is_none() {
+            path.set_extension("tex");
+        }
+        Uri::from_file_path(path.clean()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use texlab_distro::{Language, Resolver};
+    use texlab_protocol::Options;
+
+    fn parse(path: &str, text: &str) -> LatexMagicCommentInfo {
+        let uri = Uri::from_file_path(env::temp_dir().join(path)).unwrap();
+        let input = SyntaxTreeInput {
+            options: &Options::default(),
+            resolver: &Resolver::default(),
+            uri: &uri,
+            text,
+            language: Language::Latex,
+        };
+        LatexMagicCommentInfo::parse(input)
+    }
+
+    #[test]
+    fn root_relative_path() {
+        let info = parse(
+            "chapters/foo.tex",
+            "% !TEX root = ../main.tex\n\\section{Foo}",
+        );
+        let expected = Uri::from_file_path(env::temp_dir().join("main.tex")).unwrap();
+        assert_eq!(info.root, Some(expected));
+    }
+
+    #[test]
+    fn root_without_extension() {
+        let info = parse("foo.tex", "%!TeX root=main");
+        let expected = Uri::from_file_path(env::temp_dir().join("main.tex")).unwrap();
+        assert_eq!(info.root, Some(expected));
+    }
+
+    #[test]
+    fn comments() {
+        let info = parse(
+            "foo.tex",
+            "% !TEX program = lualatex\n% !BIB program = biber",
+        );
+        assert_eq!(
+            info.comments,
+            vec![
+                LatexMagicComment {
+                    line: 0,
+                    program: "TEX".into(),
+                    key: "program".into(),
+                    value: "lualatex".into(),
+                },
+                LatexMagicComment {
+                    line: 1,
+                    program: "BIB".into(),
+                    key: "program".into(),
+                    value: "biber".into(),
+                },
+            ]
+        );
+        assert_eq!(info.root, None);
+        assert_eq!(info.tex_program, Some("lualatex".into()));
+        assert_eq!(info.bib_program, Some("biber".into()));
+    }
+
+    #[test]
+    fn program_case() {
+        let info = parse("foo.tex", "% !TeX program = XeLaTeX");
+        assert_eq!(info.tex_program, Some("xelatex".into()));
+        assert_eq!(info.bib_program, None);
+    }
+
+    #[test]
+    fn ordinary_comment() {
+        let info = parse(
+            "foo.tex",
+            "% TEX root = main.tex\n% !TEX root\n\\foo % !TEX root = x",
+        );
+        assert!(info.comments.is_empty());
+        assert_eq!(info.root, None);
+    }
+}
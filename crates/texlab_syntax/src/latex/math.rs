@@ -1,10 +1,18 @@
 use super::ast::*;
 use crate::language::*;
 use crate::text::SyntaxNode;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use texlab_protocol::Range;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LatexMode {
+    Text,
+    Math,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexEquation {
     pub left: Arc<LatexCommand>,
     pub right: Arc<LatexCommand>,
@@ -38,7 +46,7 @@ impl SyntaxNode for LatexEquation {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexInline {
     pub left: Arc<LatexMath>,
     pub right: Arc<LatexMath>,
@@ -100,7 +108,7 @@ impl LatexVisitor for LatexInlineAnalyzer {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexMathOperator {
     pub command: Arc<LatexCommand>,
     pub definition: Arc<LatexCommand>,
@@ -144,7 +152,7 @@ impl SyntaxNode for LatexMathOperator {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexTheoremDefinition {
     pub command: Arc<LatexCommand>,
     pub index: usize,
@@ -179,7 +187,7 @@ impl SyntaxNode for LatexTheoremDefinition {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexMathInfo {
     pub equations: Vec<LatexEquation>,
     pub inlines: Vec<LatexInline>,
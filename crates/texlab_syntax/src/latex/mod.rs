@@ -3,17 +3,23 @@ mod env;
 mod finder;
 mod glossary;
 mod lexer;
+mod magic;
 mod math;
 mod parser;
 mod printer;
+mod register;
+mod signature;
 mod structure;
 
 pub use self::ast::*;
 pub use self::env::*;
 pub use self::finder::LatexNode;
 pub use self::glossary::*;
+pub use self::magic::*;
 pub use self::math::*;
 pub use self::printer::LatexPrinter;
+pub use self::register::*;
+pub use self::signature::*;
 pub use self::structure::*;
 
 use self::finder::LatexFinder;
@@ -23,6 +29,7 @@ use super::language::*;
 use super::text::SyntaxNode;
 use super::SyntaxTreeInput;
 use path_clean::PathClean;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
 use texlab_distro::Resolver;
@@ -68,7 +75,7 @@ impl LatexVisitor for LatexCommandAnalyzer {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexCitation {
     pub command: Arc<LatexCommand>,
     pub index: usize,
@@ -101,7 +108,7 @@ impl SyntaxNode for LatexCitation {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexInclude {
     pub command: Arc<LatexCommand>,
     pub index: usize,
@@ -215,17 +222,59 @@ impl SyntaxNode for LatexInclude {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct LatexGraphicsPath {
+    pub command: Arc<LatexCommand>,
+}
+
+impl LatexGraphicsPath {
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        if let Some(group) = self.command.args.first() {
+            for child in &group.children {
+                if let LatexContent::Group(group) = child {
+                    let path = extract_group(group);
+                    if !path.is_empty() {
+                        paths.push(path);
+                    }
+                }
+            }
+        }
+        paths
+    }
+
+    fn parse(commands: &[Arc<LatexCommand>]) -> Vec<Self> {
+        commands
+            .iter()
+            .filter(|command| command.name.text() == "\\graphicspath" && !command.args.is_empty())
+            .map(|command| Self {
+                command: Arc::clone(command),
+            })
+            .collect()
+    }
+}
+
+impl SyntaxNode for LatexGraphicsPath {
+    fn range(&self) -> Range {
+        self.command.range()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexCommandDefinition {
     pub command: Arc<LatexCommand>,
     pub definition: Arc<LatexCommand>,
     pub definition_index: usize,
     pub implementation: Arc<LatexGroup>,
     pub implementation_index: usize,
-    pub argument_count_index: usize,
+    pub signature: LatexSignature,
 }
 
 impl LatexCommandDefinition {
+    pub fn argument_count(&self) -> usize {
+        self.signature.mandatory_parameters().count()
+    }
+
     fn parse(commands: &[Arc<LatexCommand>]) -> Vec<Self> {
         let mut definitions = Vec::new();
         for command in commands {
@@ -233,6 +282,7 @@ impl LatexCommandDefinition {
                 name,
                 definition_index,
                 argument_count_index,
+                argument_spec_index,
                 implementation_index,
             } in &LANGUAGE_DATA.command_definition_commands
             {
@@ -242,13 +292,15 @@ impl LatexCommandDefinition {
                 {
                     let definition = command.args[0].children.iter().next();
                     if let Some(LatexContent::Command(definition)) = definition {
+                        let signature =
+                            parse_signature(command, *argument_count_index, *argument_spec_index);
                         definitions.push(Self {
                             command: Arc::clone(command),
                             definition: Arc::clone(definition),
                             definition_index: *definition_index,
                             implementation: Arc::clone(&command.args[*implementation_index]),
                             implementation_index: *implementation_index,
-                            argument_count_index: *argument_count_index,
+                            signature,
                         })
                     }
                 }
@@ -264,45 +316,261 @@ impl SyntaxNode for LatexCommandDefinition {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct LatexEnvironmentDefinition {
+    pub command: Arc<LatexCommand>,
+    pub name_index: usize,
+    pub begin: Arc<LatexGroup>,
+    pub end: Arc<LatexGroup>,
+    pub signature: LatexSignature,
+}
+
+impl LatexEnvironmentDefinition {
+    pub fn name(&self) -> &LatexToken {
+        self.command.extract_word(self.name_index).unwrap()
+    }
+
+    pub fn argument_count(&self) -> usize {
+        self.signature.mandatory_parameters().count()
+    }
+
+    fn parse(commands: &[Arc<LatexCommand>]) -> Vec<Self> {
+        let mut definitions = Vec::new();
+        for command in commands {
+            for LatexEnvironmentDefinitionCommand {
+                name,
+                name_index,
+                argument_count_index,
+                argument_spec_index,
+                begin_index,
+                end_index,
+            } in &LANGUAGE_DATA.environment_definition_commands
+            {
+                if command.name.text() == name
+                    && command.has_word(*name_index)
+                    && command.args.len() > *begin_index
+                    && command.args.len() > *end_index
+                {
+                    definitions.push(Self {
+                        command: Arc::clone(command),
+                        name_index: *name_index,
+                        begin: Arc::clone(&command.args[*begin_index]),
+                        end: Arc::clone(&command.args[*end_index]),
+                        signature: parse_signature(
+                            command,
+                            *argument_count_index,
+                            *argument_spec_index,
+                        ),
+                    });
+                }
+            }
+        }
+        definitions
+    }
+}
+
+impl SyntaxNode for LatexEnvironmentDefinition {
+    fn range(&self) -> Range {
+        self.command.range()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct LatexPrimitiveDefinition {
+    pub command: Arc<LatexCommand>,
+    pub definition: Arc<LatexCommand>,
+    pub kind: LatexPrimitiveDefinitionKind,
+    pub implementation: Option<Arc<LatexGroup>>,
+    pub target: Option<Arc<LatexCommand>>,
+    pub signature: LatexSignature,
+}
+
+impl LatexPrimitiveDefinition {
+    pub fn argument_count(&self) -> usize {
+        self.signature.mandatory_parameters().count()
+    }
+
+    fn parse(root: &LatexRoot) -> Vec<Self> {
+        let mut definitions = Vec::new();
+        Self::parse_content(&root.children, &mut definitions);
+        definitions
+    }
+
+    fn parse_content(children: &[LatexContent], definitions: &mut Vec<Self>) {
+        for (i, child) in children.iter().enumerate() {
+            match child {
+                LatexContent::Command(command) => {
+                    definitions.extend(Self::parse_single(command, &children[i + 1..]));
+                    for group in &command.groups {
+                        Self::parse_content(&group.children, definitions);
+                    }
+                }
+                LatexContent::Group(group) => Self::parse_content(&group.children, definitions),
+                LatexContent::Text(_) | LatexContent::Comma(_) | LatexContent::Math(_) => (),
+            }
+        }
+    }
+
+    fn parse_single(command: &Arc<LatexCommand>, siblings: &[LatexContent]) -> Option<Self> {
+        let kind = LANGUAGE_DATA
+            .primitive_definition_commands
+            .iter()
+            .find(|cmd| cmd.name == command.name.text())?
+            .kind;
+
+        let definition = match siblings.first() {
+            Some(LatexContent::Command(definition)) if command.groups.is_empty() => definition,
+            _ => return None,
+        };
+
+        match kind {
+            LatexPrimitiveDefinitionKind::Def => {
+                let (implementation, argument_count) = match definition.args.first() {
+                    Some(implementation) => (implementation, 0),
+                    None => match &siblings[1..] {
+                        [LatexContent::Text(parameters), LatexContent::Group(implementation), ..] => {
+                            (implementation, Self::parameter_count(parameters))
+                        }
+                        _ => return None,
+                    },
+                };
+
+                Some(Self {
+                    command: Arc::clone(command),
+                    definition: Arc::clone(definition),
+                    kind,
+                    implementation: Some(Arc::clone(implementation)),
+                    target: None,
+                    signature: LatexSignature::new(argument_count, None),
+                })
+            }
+            LatexPrimitiveDefinitionKind::Let => {
+                let target = match &siblings[1..] {
+                    [LatexContent::Command(target), ..] => target,
+                    [LatexContent::Text(text), LatexContent::Command(target), ..]
+                        if text.words.len() == 1 && text.words[0].text() == "=" =>
+                    {
+                        target
+                    }
+                    _ => return None,
+                };
+
+                Some(Self {
+                    command: Arc::clone(command),
+                    definition: Arc::clone(definition),
+                    kind,
+                    implementation: None,
+                    target: Some(Arc::clone(target)),
+                    signature: LatexSignature::default(),
+                })
+            }
+        }
+    }
+
+    fn parameter_count(parameters: &LatexText) -> usize {
+        parameters
+            .words
+            .iter()
+            .map(|word| word.text().matches('#').count())
+            .sum()
+    }
+}
+
+impl SyntaxNode for LatexPrimitiveDefinition {
+    fn range(&self) -> Range {
+        let end = match (&self.implementation, &self.target) {
+            (Some(implementation), _) => implementation.end(),
+            (None, Some(target)) => target.end(),
+            (None, None) => self.definition.end(),
+        };
+        Range::new(self.command.start(), end)
+    }
+}
+
+fn parse_signature(
+    command: &LatexCommand,
+    argument_count_index: usize,
+    argument_spec_index: Option<usize>,
+) -> LatexSignature {
+    if let Some(index) = argument_spec_index {
+        return command
+            .args
+            .get(index)
+            .map(|group| LatexSignature::parse(&extract_group(group)))
+            .unwrap_or_default();
+    }
+
+    let options = &command.options;
+    let count = options
+        .get(argument_count_index)
+        .and_then(|group| match group.children.as_slice() {
+            [LatexContent::Text(text)] if text.words.len() == 1 => {
+                text.words[0].text().parse().ok()
+            }
+            _ => None,
+        })
+        .unwrap_or(0);
+
+    let default = options
+        .get(argument_count_index + 1)
+        .map(|group| extract_group(group));
+    LatexSignature::new(count, default)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexSyntaxTree {
     pub root: Arc<LatexRoot>,
     pub commands: Vec<Arc<LatexCommand>>,
     pub includes: Vec<LatexInclude>,
+    pub graphics_paths: Vec<LatexGraphicsPath>,
     pub components: Vec<String>,
     pub env: LatexEnvironmentInfo,
     pub structure: LatexStructureInfo,
     pub citations: Vec<LatexCitation>,
     pub math: LatexMathInfo,
     pub command_definitions: Vec<LatexCommandDefinition>,
+    pub environment_definitions: Vec<LatexEnvironmentDefinition>,
+    pub primitive_definitions: Vec<LatexPrimitiveDefinition>,
     pub glossary: LatexGlossaryInfo,
+    pub registers: LatexRegisterInfo,
+    pub magic: LatexMagicCommentInfo,
 }
 
 impl LatexSyntaxTree {
     pub fn parse(input: SyntaxTreeInput) -> Self {
-        let lexer = LatexLexer::new(input.text);
+        let lexer = LatexLexer::new(input.text, input.options);
         let mut parser = LatexParser::new(lexer);
         let root = Arc::new(parser.root());
         let commands = LatexCommandAnalyzer::parse(Arc::clone(&root));
         let includes = LatexInclude::parse(input, &commands);
+        let graphics_paths = LatexGraphicsPath::parse(&commands);
         let components = includes.iter().flat_map(LatexInclude::components).collect();
         let env = LatexEnvironmentInfo::parse(&commands);
         let structure = LatexStructureInfo::parse(&commands);
         let citations = LatexCitation::parse(&commands);
         let math = LatexMathInfo::parse(Arc::clone(&root), &commands);
         let command_definitions = LatexCommandDefinition::parse(&commands);
+        let environment_definitions = LatexEnvironmentDefinition::parse(&commands);
+        let primitive_definitions = LatexPrimitiveDefinition::parse(&root);
         let glossary = LatexGlossaryInfo::parse(&commands);
+        let registers = LatexRegisterInfo::parse(&commands);
+        let magic = LatexMagicCommentInfo::parse(input);
         Self {
             root,
             commands,
             includes,
+            graphics_paths,
             components,
             env,
             structure,
             citations,
             math,
             command_definitions,
+            environment_definitions,
+            primitive_definitions,
             glossary,
+            registers,
+            magic,
         }
     }
 
@@ -354,6 +622,51 @@ impl LatexSyntaxTree {
                 .any(|env| env.range().contains(item.start()))
     }
 
+    pub fn math_range(&self, position: Position) -> Option<Range> {
+        let inlines = self.math.inlines.iter().map(LatexInline::range);
+        let equations = self.math.equations.iter().map(LatexEquation::range);
+        let environments = self
+            .env
+            .environments
+            .iter()
+            .filter(|env| env.left.is_math())
+            .map(LatexEnvironment::range);
+
+        inlines
+            .chain(equations)
+            .chain(environments)
+            .filter(|range| range.contains_exclusive(position))
+            .min_by_key(|range| range.start)
+    }
+
+    pub fn mode(&self, position: Position) -> LatexMode {
+        let math_range = match self.math_range(position) {
+            Some(range) => range,
+            None => return LatexMode::Text,
+        };
+
+        let in_text = self.find(position).into_iter().any(|node| match node {
+            LatexNode::Command(command) => {
+                math_range.contains_exclusive(command.start())
+                    && LANGUAGE_DATA
+                        .math_text_commands
+                        .iter()
+                        .any(|name| name == command.name.text())
+                    && command
+                        .args
+                        .iter()
+                        .any(|arg| arg.range().contains_exclusive(position))
+            }
+            _ => false,
+        });
+
+        if in_text {
+            LatexMode::Text
+        } else {
+            LatexMode::Math
+        }
+    }
+
     pub fn is_direct_child(&self, environment: &LatexEnvironment, position: Position) -> bool {
         environment.range().contains(position)
             && !self
@@ -377,3 +690,142 @@ pub fn extract_group(content: &LatexGroup) -> String {
     }
     printer.output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use texlab_distro::Language;
+    use texlab_protocol::Options;
+
+    fn parse(text: &str) -> LatexSyntaxTree {
+        let uri = Uri::from_file_path(env::temp_dir().join("foo.tex")).unwrap();
+        let input = SyntaxTreeInput {
+            options: &Options::default(),
+            resolver: &Resolver::default(),
+            uri: &uri,
+            text,
+            language: Language::Latex,
+        };
+        LatexSyntaxTree::parse(input)
+    }
+
+    #[test]
+    fn mode_inline() {
+        let tree = parse("foo $\\alpha$ bar");
+        assert_eq!(tree.mode(Position::new(0, 2)), LatexMode::Text);
+        assert_eq!(tree.mode(Position::new(0, 8)), LatexMode::Math);
+    }
+
+    #[test]
+    fn mode_environment() {
+        let tree = parse("\\begin{align}\n\\al\n\\end{align}\n\\sec");
+        assert_eq!(tree.mode(Position::new(1, 3)), LatexMode::Math);
+        assert_eq!(tree.mode(Position::new(3, 4)), LatexMode::Text);
+    }
+
+    #[test]
+    fn mode_text_inside_math() {
+        let tree = parse("\\[ x \\text{if \\emph{y}} \\]");
+        assert_eq!(tree.mode(Position::new(0, 4)), LatexMode::Math);
+        assert_eq!(tree.mode(Position::new(0, 16)), LatexMode::Text);
+    }
+
+    #[test]
+    fn registers() {
+        let tree =
+            parse("\\newcounter{foo}\n\\newlength{\\bar}\n\\setlength{\\bar}{1cm}\n\\usebox{baz}");
+        let names: Vec<_> = tree
+            .registers
+            .registers
+            .iter()
+            .map(|register| (register.name().text(), register.kind, register.definition))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("foo", LatexRegisterKind::Counter, true),
+                ("\\bar", LatexRegisterKind::Length, true),
+                ("\\bar", LatexRegisterKind::Length, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn document_command_definition() {
+        let tree = parse("\\NewDocumentCommand{\\foo}{ m O{default} s }{#1}");
+        let definition = &tree.command_definitions[0];
+        assert_eq!(definition.definition.name.text(), "\\foo");
+        assert_eq!(definition.signature.parameters.len(), 3);
+        assert_eq!(definition.argument_count(), 1);
+    }
+
+    #[test]
+    fn command_definition_with_default() {
+        let tree = parse("\\newcommand{\\foo}[2][bar]{#1 #2}");
+        let definition = &tree.command_definitions[0];
+        assert_eq!(
+            definition.signature,
+            LatexSignature::new(2, Some("bar".into()))
+        );
+        assert_eq!(definition.argument_count(), 1);
+    }
+
+    #[test]
+    fn document_environment_definition() {
+        let tree = parse("\\NewDocumentEnvironment{foo}{m m}{\\begin{center}}{\\end{center}}");
+        let definition = &tree.environment_definitions[0];
+        assert_eq!(definition.name().text(), "foo");
+        assert_eq!(definition.argument_count(), 2);
+    }
+
+    #[test]
+    fn environment_definition() {
+        let tree = parse("\\newenvironment{foo}[2][x]{\\begin{center}#1}{\\end{center}}");
+        let definition = &tree.environment_definitions[0];
+        assert_eq!(definition.name().text(), "foo");
+        assert_eq!(definition.argument_count(), 1);
+        assert_eq!(extract_group(&definition.end), "\\end{center}");
+    }
+
+    #[test]
+    fn primitive_definitions() {
+        let tree = parse("\\def\\foo#1#2{#1#2}\n{\\gdef\\bar{bar}}\n\\let\\baz=\\foo\n\\def\\qux");
+        let definitions: Vec<_> = tree
+            .primitive_definitions
+            .iter()
+            .map(|def| (def.definition.name.text(), def.kind, def.argument_count()))
+            .collect();
+        assert_eq!(
+            definitions,
+            vec![
+                ("\\foo", LatexPrimitiveDefinitionKind::Def, 2),
+                ("\\bar", LatexPrimitiveDefinitionKind::Def, 0),
+                ("\\baz", LatexPrimitiveDefinitionKind::Let, 0),
+            ]
+        );
+        assert_eq!(
+            tree.primitive_definitions[0].range(),
+            Range::new_simple(0, 0, 0, 18)
+        );
+        assert_eq!(
+            tree.primitive_definitions[2].range(),
+            Range::new_simple(2, 0, 2, 13)
+        );
+    }
+
+    #[test]
+    fn verbatim() {
+        let tree = parse(
+            "\\begin{lstlisting}[language=TeX]\n\\label{foo}\n\\end{lstlisting}\n\\verb|\\label{bar}|",
+        );
+        let names: Vec<_> = tree
+            .commands
+            .iter()
+            .map(|command| command.name.text())
+            .collect();
+        assert_eq!(names, vec!["\\begin", "\\end", "\\verb"]);
+        assert!(tree.structure.labels.is_empty());
+        assert_eq!(tree.env.environments.len(), 1);
+    }
+}
@@ -32,8 +32,7 @@ impl LatexPrinter {
     fn print_token(&mut self, token: &LatexToken) {
         self.synchronize(token.start());
         self.output.push_str(token.text());
-        self.position.character += token.end().character - token.start().character;
-        self.synchronize(token.end());
+        self.position = token.end();
     }
 }
 
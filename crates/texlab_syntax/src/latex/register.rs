@@ -0,0 +1,86 @@
+use super::ast::*;
+use crate::language::*;
+use crate::text::SyntaxNode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use texlab_protocol::Range;
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct LatexRegister {
+    pub command: Arc<LatexCommand>,
+    index: usize,
+    pub kind: LatexRegisterKind,
+    pub definition: bool,
+}
+
+impl SyntaxNode for LatexRegister {
+    fn range(&self) -> Range {
+        self.command.range()
+    }
+}
+
+impl LatexRegister {
+    pub fn name(&self) -> &LatexToken {
+        Self::extract_name(&self.command, self.index, self.kind).unwrap()
+    }
+
+    fn extract_name(
+        command: &LatexCommand,
+        index: usize,
+        kind: LatexRegisterKind,
+    ) -> Option<&LatexToken> {
+        match kind {
+            LatexRegisterKind::Counter => command.extract_word(index),
+            LatexRegisterKind::Length | LatexRegisterKind::Box => {
+                match command.args.get(index)?.children.as_slice() {
+                    [LatexContent::Command(name)] => Some(&name.name),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    fn parse(commands: &[Arc<LatexCommand>]) -> Vec<Self> {
+        let mut registers = Vec::new();
+        for command in commands {
+            for LatexRegisterCommand {
+                name,
+                index,
+                kind,
+                definition,
+            } in &LANGUAGE_DATA.register_commands
+            {
+                if command.name.text() == name
+                    && Self::extract_name(command, *index, *kind).is_some()
+                {
+                    registers.push(Self {
+                        command: Arc::clone(command),
+                        index: *index,
+                        kind: *kind,
+                        definition: *definition,
+                    });
+                }
+            }
+        }
+        registers
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct LatexRegisterInfo {
+    pub registers: Vec<LatexRegister>,
+}
+
+impl LatexRegisterInfo {
+    pub fn parse(commands: &[Arc<LatexCommand>]) -> Self {
+        Self {
+            registers: LatexRegister::parse(commands),
+        }
+    }
+
+    pub fn definitions(&self, kind: LatexRegisterKind) -> impl Iterator<Item = &LatexRegister> {
+        self.registers
+            .iter()
+            .filter(move |register| register.kind == kind && register.definition)
+    }
+}
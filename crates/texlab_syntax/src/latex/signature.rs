@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum LatexParameter {
+    Mandatory {
+        delimiters: (String, String),
+        default: Option<String>,
+    },
+    Optional {
+        delimiters: (String, String),
+        default: Option<String>,
+    },
+    Star,
+    Token(String),
+    Verbatim,
+    Embellishment(String),
+    Body,
+}
+
+impl LatexParameter {
+    pub fn is_mandatory(&self) -> bool {
+        match self {
+            LatexParameter::Mandatory { .. } | LatexParameter::Verbatim => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+pub struct LatexSignature {
+    pub parameters: Vec<LatexParameter>,
+}
+
+impl LatexSignature {
+    pub fn new(argument_count: usize, default: Option<String>) -> Self {
+        let mut parameters = Vec::new();
+        let mut mandatory_count = argument_count;
+        if let Some(default) = default {
+            if argument_count > 0 {
+                parameters.push(LatexParameter::Optional {
+                    delimiters: brackets(),
+                    default: Some(default),
+                });
+                mandatory_count -= 1;
+            }
+        }
+
+        for _ in 0..mandatory_count {
+            parameters.push(LatexParameter::Mandatory {
+                delimiters: braces(),
+                default: None,
+            });
+        }
+        Self { parameters }
+    }
+
+    pub fn parse(spec: &str) -> Self {
+        let mut chars = spec.chars().peekable();
+        let mut parameters = Vec::new();
+        loop {
+            skip_whitespace(&mut chars);
+            let parameter = match chars.next() {
+                Some('+') | Some('!') => continue,
+                Some('>') => {
+                    read_token(&mut chars);
+                    continue;
+                }
+                Some('m') => LatexParameter::Mandatory {
+                    delimiters: braces(),
+                    default: None,
+                },
+                Some('r') => LatexParameter::Mandatory {
+                    delimiters: read_delimiters(&mut chars),
+                    default: None,
+                },
+                Some('R') => LatexParameter::Mandatory {
+                    delimiters: read_delimiters(&mut chars),
+                    default: Some(read_token(&mut chars)),
+                },
+                Some('o') => LatexParameter::Optional {
+                    delimiters: brackets(),
+                    default: None,
+                },
+                Some('d') => LatexParameter::Optional {
+                    delimiters: read_delimiters(&mut chars),
+                    default: None,
+                },
+                Some('O') => LatexParameter::Optional {
+                    delimiters: brackets(),
+                    default: Some(read_token(&mut chars)),
+                },
+                Some('D') => LatexParameter::Optional {
+                    delimiters: read_delimiters(&mut chars),
+                    default: Some(read_token(&mut chars)),
+                },
+                Some('s') => LatexParameter::Star,
+                Some('t') => LatexParameter::Token(read_token(&mut chars)),
+                Some('v') => LatexParameter::Verbatim,
+                Some('e') => LatexParameter::Embellishment(read_token(&mut chars)),
+                Some('E') => {
+                    let tokens = read_token(&mut chars);
+                    read_token(&mut chars);
+                    LatexParameter::Embellishment(tokens)
+                }
+                Some('b') => LatexParameter::Body,
+                Some(_) => continue,
+                None => break,
+            };
+            parameters.push(parameter);
+        }
+        Self { parameters }
+    }
+
+    pub fn mandatory_parameters(&self) -> impl Iterator<Item = &LatexParameter> {
+        self.parameters
+            .iter()
+            .filter(|parameter| parameter.is_mandatory())
+    }
+}
+
+fn braces() -> (String, String) {
+    ("{".into(), "}".into())
+}
+
+fn brackets() -> (String, String) {
+    ("[".into(), "]".into())
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.peek().map_or(false, |c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+fn read_delimiters(chars: &mut Peekable<Chars>) -> (String, String) {
+    let left = read_token(chars);
+    let right = read_token(chars);
+    (left, right)
+}
+
+fn read_token(chars: &mut Peekable<Chars>) -> String {
+    skip_whitespace(chars);
+    let mut token = String::new();
+    match chars.next() {
+        Some('{') => {
+            let mut depth = 0;
+            for c in chars.by_ref() {
+                match c {
+                    '{' => depth += 1,
+                    '}' if depth == 0 => break,
+                    '}' => depth -= 1,
+                    _ => (),
+                }
+                token.push(c);
+            }
+        }
+        Some('\\') => {
+            token.push('\\');
+            while let Some(c) = chars.peek().copied() {
+                if !c.is_alphabetic() {
+                    if token.len() == 1 {
+                        token.push(c);
+                        chars.next();
+                    }
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+        }
+        Some(c) => token.push(c),
+        None => (),
+    }
+    token
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mandatory(left: &str, right: &str, default: Option<&str>) -> LatexParameter {
+        LatexParameter::Mandatory {
+            delimiters: (left.into(), right.into()),
+            default: default.map(Into::into),
+        }
+    }
+
+    fn optional(left: &str, right: &str, default: Option<&str>) -> LatexParameter {
+        LatexParameter::Optional {
+            delimiters: (left.into(), right.into()),
+            default: default.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn argument_count() {
+        let signature = LatexSignature::new(3, Some("foo".into()));
+        assert_eq!(
+            signature.parameters,
+            vec![
+                optional("[", "]", Some("foo")),
+                mandatory("{", "}", None),
+                mandatory("{", "}", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn spec_simple() {
+        let signature = LatexSignature::parse(" m O{default} s ");
+        assert_eq!(
+            signature.parameters,
+            vec![
+                mandatory("{", "}", None),
+                optional("[", "]", Some("default")),
+                LatexParameter::Star,
+            ]
+        );
+        assert_eq!(signature.mandatory_parameters().count(), 1);
+    }
+
+    #[test]
+    fn spec_delimited() {
+        let signature = LatexSignature::parse("r() D<>{x{y}} t+ +o >{\\SplitList{;}}m e{^_}");
+        assert_eq!(
+            signature.parameters,
+            vec![
+                mandatory("(", ")", None),
+                optional("<", ">", Some("x{y}")),
+                LatexParameter::Token("+".into()),
+                optional("[", "]", None),
+                mandatory("{", "}", None),
+                LatexParameter::Embellishment("^_".into()),
+            ]
+        );
+    }
+}
@@ -2,15 +2,16 @@ use super::ast::*;
 use crate::language::*;
 use crate::text::{CharStream, SyntaxNode};
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use texlab_protocol::{Range, RangeExt};
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexSection {
     pub command: Arc<LatexCommand>,
     pub index: usize,
     pub level: i32,
-    pub prefix: &'static str,
+    pub prefix: String,
 }
 
 impl LatexSection {
@@ -29,7 +30,7 @@ impl LatexSection {
                         command: Arc::clone(command),
                         index: *index,
                         level: *level,
-                        prefix: prefix.as_ref(),
+                        prefix: prefix.clone(),
                     })
                 }
             }
@@ -56,7 +57,7 @@ impl SyntaxNode for LatexSection {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexLabel {
     pub command: Arc<LatexCommand>,
     index: usize,
@@ -91,7 +92,7 @@ impl SyntaxNode for LatexLabel {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexLabelNumbering {
     pub command: Arc<LatexCommand>,
     pub number: String,
@@ -164,7 +165,7 @@ impl LatexLabelNumbering {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexCaption {
     pub command: Arc<LatexCommand>,
     pub index: usize,
@@ -191,7 +192,7 @@ impl SyntaxNode for LatexCaption {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexItem {
     pub command: Arc<LatexCommand>,
 }
@@ -227,7 +228,7 @@ impl SyntaxNode for LatexItem {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct LatexStructureInfo {
     pub sections: Vec<LatexSection>,
     pub labels: Vec<LatexLabel>,
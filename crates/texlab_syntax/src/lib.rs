@@ -1,15 +1,18 @@
 mod bibtex;
+mod keyval;
 mod language;
 mod latex;
 mod lsp_kind;
 mod text;
 
 pub use self::bibtex::*;
+pub use self::keyval::*;
 pub use self::language::*;
 pub use self::latex::*;
 pub use self::lsp_kind::*;
 pub use self::text::*;
 
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use texlab_distro::{Language, Resolver};
 use texlab_protocol::{Options, Uri};
@@ -39,7 +42,7 @@ impl<'a> SyntaxTreeInput<'a> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum SyntaxTree {
     Latex(Box<LatexSyntaxTree>),
     Bibtex(Box<BibtexSyntaxTree>),
@@ -23,7 +23,9 @@ pub enum Structure {
     Entry(BibtexEntryTypeCategory),
     Field,
     Argument,
+    Key,
     GlossaryEntry,
+    Register,
 }
 
 impl Structure {
@@ -55,7 +57,9 @@ impl Structure {
             Self::Entry(BibtexEntryTypeCategory::Thesis) => CompletionItemKind::Unit,
             Self::Field => CompletionItemKind::Field,
             Self::Argument => CompletionItemKind::Value,
+            Self::Key => CompletionItemKind::Property,
             Self::GlossaryEntry => CompletionItemKind::Keyword,
+            Self::Register => CompletionItemKind::Variable,
         }
     }
 
@@ -87,7 +91,9 @@ impl Structure {
             Self::Entry(BibtexEntryTypeCategory::Thesis) => SymbolKind::Object,
             Self::Field => SymbolKind::Field,
             Self::Argument => SymbolKind::Number,
+            Self::Key => SymbolKind::Key,
             Self::GlossaryEntry => unimplemented!(),
+            Self::Register => SymbolKind::Variable,
         }
     }
 }
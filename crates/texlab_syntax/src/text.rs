@@ -59,6 +59,10 @@ impl<'a> CharStream<'a> {
         self.chars.peek().map(|(_, c)| *c)
     }
 
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.current_index..]
+    }
+
     pub fn satifies<P: FnOnce(&char) -> bool>(&mut self, predicate: P) -> bool {
         self.peek().filter(predicate).is_some()
     }
@@ -131,6 +135,12 @@ impl<'a> CharStream<'a> {
         stream.seek(range.end);
         stream.end_span().text
     }
+
+    pub fn end_position(text: &'a str) -> Position {
+        let mut stream = Self::new(text);
+        while stream.next().is_some() {}
+        stream.current_position
+    }
 }
 
 impl<'a> Iterator for CharStream<'a> {
@@ -265,4 +275,12 @@ mod tests {
             span
         );
     }
+
+    #[test]
+    fn end_position() {
+        assert_eq!(
+            CharStream::end_position("foo\nbar\n\u{1F600}"),
+            Position::new(2, 1)
+        );
+    }
 }
@@ -18,6 +18,8 @@ async fn create_scenario(
                 args: None,
                 on_save: Some(build_on_save),
                 output_directory: None,
+                aux_directory: None,
+                engine_args: None,
             }),
             ..LatexOptions::default()
         }),
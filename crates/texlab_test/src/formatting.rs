@@ -14,6 +14,7 @@ pub async fn run_bibtex(
         *scenario.client.options.lock().await = Options {
             bibtex: Some(BibtexOptions {
                 formatting: options,
+                ..BibtexOptions::default()
             }),
             latex: None,
         };
@@ -35,3 +36,26 @@ pub async fn run_bibtex(
         .unwrap();
     (scenario, edits)
 }
+
+pub async fn run_bibtex_range(file: &'static str, range: Range) -> (Scenario, Vec<TextEdit>) {
+    let scenario = Scenario::new("formatting/bibtex", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open(file).await;
+
+    let params = DocumentRangeFormattingParams {
+        text_document: TextDocumentIdentifier::new(scenario.uri(file).into()),
+        range,
+        options: FormattingOptions {
+            tab_size: 4,
+            insert_spaces: true,
+            properties: HashMap::new(),
+        },
+    };
+
+    let edits = scenario
+        .server
+        .execute(|svr| svr.range_formatting(params))
+        .await
+        .unwrap();
+    (scenario, edits)
+}
@@ -25,7 +25,7 @@ impl Scenario {
         let distribution: Arc<Box<dyn Distribution>> = if use_distribution {
             let mut guard = DISTRIBUTION.lock().await;
             if guard.is_none() {
-                *guard = Some(Arc::new(Distribution::detect().await));
+                *guard = Some(Arc::new(Distribution::detect(None).await));
             }
             Arc::clone(guard.as_ref().unwrap())
         } else {
@@ -40,7 +40,7 @@ impl Scenario {
         copy_dir(source, directory.path()).unwrap();
 
         let client = Arc::new(MockLspClient::new());
-        let server = LatexLspServer::new(Arc::clone(&client), Arc::clone(&distribution));
+        let server = LatexLspServer::new(Arc::clone(&client), Arc::clone(&distribution), None);
         Self {
             distribution,
             directory,
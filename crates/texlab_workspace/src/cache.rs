@@ -0,0 +1,268 @@
+use crate::document::Document;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use texlab_distro::{Language, Resolver};
+use texlab_protocol::{Options, Uri};
+use texlab_syntax::SyntaxTree;
+
+const DOCUMENTS_DIRECTORY: &str = "documents";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry<T> {
+    uri: Uri,
+    key: u64,
+    tree: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct CacheEntryHeader {
+    uri: Uri,
+}
+
+#[derive(Debug)]
+pub struct DocumentCache {
+    directory: PathBuf,
+    options: Options,
+    resolver: Arc<Resolver>,
+    context: u64,
+}
+
+impl DocumentCache {
+    pub fn new(directory: &Path, options: &Options, resolver: Arc<Resolver>) -> Self {
+        let context = fnv1a(&[
+            &serde_json::to_vec(options).unwrap(),
+            &serde_json::to_vec(&resolver.databases).unwrap(),
+        ]);
+
+        Self {
+            directory: directory.join(DOCUMENTS_DIRECTORY),
+            options: options.clone(),
+            resolver,
+            context,
+        }
+    }
+
+    pub fn is_valid(&self, options: &Options, resolver: &Arc<Resolver>) -> bool {
+        self.options == *options && self.resolver.databases == resolver.databases
+    }
+
+    pub fn prune(&self) {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        for file in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+            let exists = fs::read(&file)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<CacheEntryHeader>(&bytes).ok())
+                .and_then(|header| header.uri.to_file_path().ok())
+                .map_or(false, |path| path.exists());
+
+            if !exists && fs::remove_file(&file).is_err() {
+                warn!("Could not remove cache entry: {}", file.to_string_lossy());
+            }
+        }
+    }
+
+    pub fn parse(&self, uri: Uri, text: String, language: Language) -> Document {
+        let file = self
+            .directory
+            .join(format!("{:016x}.json", Self::hash(&uri)));
+        let key = self.key(&uri, &text);
+        let tree = fs::read(&file)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<CacheEntry<SyntaxTree>>(&bytes).ok())
+            .filter(|entry| entry.key == key)
+            .map(|entry| entry.tree)
+            .filter(|tree| Self::language(tree) == language);
+
+        match tree {
+            Some(tree) => Document {
+                uri,
+                text,
+                tree,
+                modified: SystemTime::now(),
+            },
+            None => {
+                let document = Document::parse(uri, text, language, &self.options, &self.resolver);
+                self.store(&file, &document.uri, key, &document.tree);
+                document
+            }
+        }
+    }
+
+    fn hash(uri: &Uri) -> u64 {
+        fnv1a(&[uri.as_str().as_bytes()])
+    }
+
+    fn key(&self, uri: &Uri, text: &str) -> u64 {
+        fnv1a(&[
+            &self.context.to_le_bytes(),
+            uri.as_str().as_bytes(),
+            text.as_bytes(),
+        ])
+    }
+
+    fn language(tree: &SyntaxTree) -> Language {
+        match tree {
+            SyntaxTree::Latex(_) => Language::Latex,
+            SyntaxTree::Bibtex(_) => Language::Bibtex,
+        }
+    }
+
+    fn store(&self, file: &Path, uri: &Uri, key: u64, tree: &SyntaxTree) {
+        let result = fs::create_dir_all(&self.directory).and_then(|()| {
+            let entry = CacheEntry {
+                uri: uri.clone(),
+                key,
+                tree,
+            };
+            let json = serde_json::to_vec(&entry).unwrap();
+            fs::write(file, json)
+        });
+
+        if result.is_err() {
+            warn!("Could not write document cache: {}", file.to_string_lossy());
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash, which unlike `DefaultHasher` stays the same across Rust versions.
+fn fnv1a(parts: &[&[u8]]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    for part in parts {
+        let length = (part.len() as u64).to_le_bytes();
+        for byte in length.iter().chain(part.iter()) {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use tempfile::tempdir;
+    use texlab_distro::DatabaseTimestamp;
+    use texlab_syntax::{LatexIncludeKind, LatexSyntaxTree};
+
+    fn uri() -> Uri {
+        Uri::from_file_path(env::temp_dir().join("foo.tex")).unwrap()
+    }
+
+    fn latex(document: &Document) -> &LatexSyntaxTree {
+        match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn parse_cached() {
+        let directory = tempdir().unwrap();
+        let resolver = Arc::new(Resolver::default());
+        let cache = DocumentCache::new(directory.path(), &Options::default(), resolver);
+        let text = "\\documentclass{article}\n\\include{bar}\n\\label{sec:foo}";
+        let parsed = cache.parse(uri(), text.into(), Language::Latex);
+        let cached = cache.parse(uri(), text.into(), Language::Latex);
+        assert_eq!(cached.tree, parsed.tree);
+
+        let tree = latex(&cached);
+        assert_eq!(tree.includes[0].kind, LatexIncludeKind::Latex);
+        assert_eq!(tree.structure.labels[0].names()[0].text(), "sec:foo");
+    }
+
+    #[test]
+    fn parse_changed_text() {
+        let directory = tempdir().unwrap();
+        let resolver = Arc::new(Resolver::default());
+        let cache = DocumentCache::new(directory.path(), &Options::default(), resolver);
+        cache.parse(uri(), "\\label{foo}".into(), Language::Latex);
+        let document = cache.parse(uri(), "\\label{bar}".into(), Language::Latex);
+        assert_eq!(
+            latex(&document).structure.labels[0].names()[0].text(),
+            "bar"
+        );
+    }
+
+    #[test]
+    fn parse_overwrite() {
+        let directory = tempdir().unwrap();
+        let resolver = Arc::new(Resolver::default());
+        let cache = DocumentCache::new(directory.path(), &Options::default(), resolver);
+        cache.parse(uri(), "\\label{foo}".into(), Language::Latex);
+        cache.parse(uri(), "\\label{bar}".into(), Language::Latex);
+        let files = fs::read_dir(directory.path().join(DOCUMENTS_DIRECTORY)).unwrap();
+        assert_eq!(files.count(), 1);
+    }
+
+    #[test]
+    fn parse_changed_databases() {
+        let directory = tempdir().unwrap();
+        let resolver = Arc::new(Resolver::default());
+        let cache = DocumentCache::new(directory.path(), &Options::default(), resolver);
+        let key = cache.key(&uri(), "\\label{foo}");
+
+        let mut resolver = Resolver::default();
+        resolver.databases = vec![DatabaseTimestamp::read(directory.path().join("ls-R"))];
+        let cache = DocumentCache::new(directory.path(), &Options::default(), Arc::new(resolver));
+        assert_ne!(cache.key(&uri(), "\\label{foo}"), key);
+    }
+
+    #[test]
+    fn prune() {
+        let directory = tempdir().unwrap();
+        let resolver = Arc::new(Resolver::default());
+        let cache = DocumentCache::new(directory.path(), &Options::default(), resolver);
+        let path = directory.path().join("foo.tex");
+        fs::write(&path, "").unwrap();
+        let existing = Uri::from_file_path(&path).unwrap();
+        let removed = Uri::from_file_path(directory.path().join("bar.tex")).unwrap();
+        cache.parse(existing.clone(), "\\label{foo}".into(), Language::Latex);
+        cache.parse(removed, "\\label{bar}".into(), Language::Latex);
+
+        cache.prune();
+        let files: Vec<_> = fs::read_dir(directory.path().join(DOCUMENTS_DIRECTORY))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(
+            files,
+            vec![cache
+                .directory
+                .join(format!("{:016x}.json", DocumentCache::hash(&existing)))]
+        );
+    }
+
+    #[test]
+    fn fnv1a_stable() {
+        assert_eq!(fnv1a(&[]), 0xcbf2_9ce4_8422_2325);
+        assert_ne!(
+            fnv1a(&["ab".as_bytes(), "c".as_bytes()]),
+            fnv1a(&["a".as_bytes(), "bc".as_bytes()])
+        );
+    }
+
+    #[test]
+    fn is_valid() {
+        let resolver = Arc::new(Resolver::default());
+        let cache =
+            DocumentCache::new(&env::temp_dir(), &Options::default(), Arc::clone(&resolver));
+        assert!(cache.is_valid(&Options::default(), &resolver));
+        assert!(cache.is_valid(&Options::default(), &Arc::new(Resolver::default())));
+
+        let mut resolver = Resolver::default();
+        resolver.databases = vec![DatabaseTimestamp::read(env::temp_dir().join("ls-R"))];
+        assert!(!cache.is_valid(&Options::default(), &Arc::new(resolver)));
+    }
+}
@@ -2,7 +2,12 @@ use super::document::Document;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use texlab_distro::Resolver;
 use texlab_protocol::{MarkupContent, MarkupKind};
 use texlab_syntax::*;
 
@@ -81,6 +86,121 @@ impl Database {
             value: desc,
         })
     }
+
+    pub fn options(
+        &self,
+        name: &str,
+        kind: LatexIncludeKind,
+        resolver: &Resolver,
+    ) -> Vec<ComponentOption> {
+        let mut options: Vec<ComponentOption> = self
+            .metadata
+            .iter()
+            .find(|metadata| metadata.name == name)
+            .map(|metadata| metadata.options.clone())
+            .unwrap_or_default();
+
+        let declared_options = kind
+            .extensions()
+            .and_then(|extensions| extensions.first())
+            .map(|extension| format!("{}.{}", name, extension))
+            .and_then(|file_name| resolver.files_by_name.get(&file_name))
+            .and_then(|path| cached_options(path))
+            .unwrap_or_default();
+
+        for option in declared_options {
+            match options.iter_mut().find(|other| other.name == option.name) {
+                Some(other) => {
+                    if other.description.is_none() {
+                        other.description = option.description;
+                    }
+                }
+                None => options.push(option),
+            }
+        }
+        options
+    }
+
+    pub fn option_documentation(
+        &self,
+        name: &str,
+        kind: LatexIncludeKind,
+        option: &str,
+        resolver: &Resolver,
+    ) -> Option<MarkupContent> {
+        let desc = self
+            .options(name, kind, resolver)
+            .into_iter()
+            .find(|opt| opt.name == option)?
+            .description?;
+
+        Some(MarkupContent {
+            kind: MarkupKind::PlainText,
+            value: desc,
+        })
+    }
+}
+
+static DECLARED_OPTIONS: Lazy<Mutex<HashMap<PathBuf, (SystemTime, Vec<ComponentOption>)>>> =
+    Lazy::new(Mutex::default);
+
+fn cached_options(path: &Path) -> Option<Vec<ComponentOption>> {
+    let modified = fs::metadata(path).and_then(|data| data.modified()).ok()?;
+    let mut cache = DECLARED_OPTIONS.lock().unwrap();
+    if let Some((time, options)) = cache.get(path) {
+        if *time == modified {
+            return Some(options.clone());
+        }
+    }
+
+    let options = declared_options(&fs::read_to_string(path).ok()?);
+    cache.insert(path.to_owned(), (modified, options.clone()));
+    Some(options)
+}
+
+fn declared_options(text: &str) -> Vec<ComponentOption> {
+    const DECLARE_OPTION: &str = "\\DeclareOption";
+    let mut options: Vec<ComponentOption> = Vec::new();
+    let mut comment: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        let trimmed_line = line.trim_start();
+        if trimmed_line.starts_with('%') {
+            let words = trimmed_line.trim_start_matches('%').trim();
+            if !words.is_empty() {
+                comment.push(words);
+            }
+            continue;
+        }
+
+        let description = if comment.is_empty() {
+            None
+        } else {
+            Some(comment.join(" "))
+        };
+        comment.clear();
+
+        let line = line.split('%').next().unwrap();
+        for (index, _) in line.match_indices(DECLARE_OPTION) {
+            let rest = &line[index + DECLARE_OPTION.len()..];
+            if !rest.starts_with('{') {
+                continue;
+            }
+
+            if let Some(end) = rest.find('}') {
+                let option = rest[1..end].trim();
+                if !option.is_empty()
+                    && !option.starts_with('\\')
+                    && options.iter().all(|other| other.name != option)
+                {
+                    options.push(ComponentOption {
+                        name: option.to_owned(),
+                        description: description.clone(),
+                    });
+                }
+            }
+        }
+    }
+    options
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -101,6 +221,41 @@ pub struct Command {
     pub parameters: Vec<Parameter>,
 }
 
+impl Command {
+    pub fn allows(&self, mode: LatexMode) -> bool {
+        COMMAND_MODES
+            .get(self.name.as_str())
+            .map_or(true, |command_mode| command_mode.allows(mode))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum CommandMode {
+    Math,
+    Text,
+}
+
+impl CommandMode {
+    fn allows(self, mode: LatexMode) -> bool {
+        match (self, mode) {
+            (CommandMode::Math, LatexMode::Text) | (CommandMode::Text, LatexMode::Math) => false,
+            _ => true,
+        }
+    }
+}
+
+static COMMAND_MODES: Lazy<HashMap<&'static str, CommandMode>> = Lazy::new(|| {
+    let math_commands = LANGUAGE_DATA
+        .math_commands
+        .iter()
+        .map(|name| (&name[1..], CommandMode::Math));
+    let text_commands = LANGUAGE_DATA
+        .text_commands
+        .iter()
+        .map(|name| (&name[1..], CommandMode::Text));
+    math_commands.chain(text_commands).collect()
+});
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Parameter(pub Vec<Argument>);
@@ -118,8 +273,92 @@ pub struct Metadata {
     pub name: String,
     pub caption: Option<String>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub options: Vec<ComponentOption>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentOption {
+    pub name: String,
+    pub description: Option<String>,
 }
 
 const JSON: &str = include_str!("components.json");
 
 pub static COMPONENT_DATABASE: Lazy<Database> = Lazy::new(|| serde_json::from_str(JSON).unwrap());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn declared_options_ignore_comments() {
+        let text = "\\DeclareOption{draft}{}\\DeclareOption{final}{}\n\
+                    % \\DeclareOption{foo}{}\n\
+                    \\DeclareOption*{}\n\
+                    \\DeclareOption{draft}{}";
+        let names: Vec<_> = declared_options(text)
+            .into_iter()
+            .map(|option| option.name)
+            .collect();
+        assert_eq!(names, vec!["draft", "final"]);
+    }
+
+    #[test]
+    fn declared_options_description() {
+        let text = "% Typeset the document\n\
+                    %% in draft mode.\n\
+                    \\DeclareOption{draft}{}\n\
+                    \\DeclareOption{final}{}";
+        assert_eq!(
+            declared_options(text),
+            vec![
+                ComponentOption {
+                    name: "draft".into(),
+                    description: Some("Typeset the document in draft mode.".into()),
+                },
+                ComponentOption {
+                    name: "final".into(),
+                    description: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn options_from_installed_file() {
+        let directory = tempdir().unwrap();
+        let path = directory.path().join("foo.sty");
+        fs::write(&path, "\\DeclareOption{bar}{}\n\\ProcessOptions").unwrap();
+        let mut files_by_name = HashMap::new();
+        files_by_name.insert("foo.sty".into(), path);
+        let resolver = Resolver::new(files_by_name);
+
+        let database = Database {
+            components: Vec::new(),
+            metadata: vec![Metadata {
+                name: "foo".into(),
+                caption: None,
+                description: None,
+                options: vec![ComponentOption {
+                    name: "bar".into(),
+                    description: Some("Baz".into()),
+                }],
+            }],
+        };
+        let options = database.options("foo", LatexIncludeKind::Package, &resolver);
+        let names: Vec<_> = options.iter().map(|option| option.name.as_str()).collect();
+        assert_eq!(names, vec!["bar"]);
+        assert_eq!(
+            database
+                .option_documentation("foo", LatexIncludeKind::Package, "bar", &resolver)
+                .map(|documentation| documentation.value),
+            Some("Baz".into())
+        );
+        assert!(database
+            .options("foo", LatexIncludeKind::Class, &resolver)
+            .is_empty());
+    }
+}
@@ -3,7 +3,9 @@ use super::workspace::{TestWorkspaceBuilder, Workspace};
 use futures::executor::block_on;
 use futures_boxed::boxed;
 use std::sync::Arc;
-use texlab_distro::{Distribution, UnknownDistribution};
+use texlab_distro::{
+    Distribution, DistributionKind, Format, LoadError, Resolver, UnknownDistribution,
+};
 use texlab_protocol::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -118,10 +120,49 @@ where
     }
 }
 
+#[derive(Debug, Default)]
+pub struct TestDistribution {
+    resolver: Arc<Resolver>,
+    formats: Vec<Format>,
+}
+
+impl TestDistribution {
+    pub fn new(resolver: Resolver) -> Self {
+        Self {
+            resolver: Arc::new(resolver),
+            formats: Vec::new(),
+        }
+    }
+
+    pub fn with_formats(self, formats: Vec<Format>) -> Self {
+        Self { formats, ..self }
+    }
+}
+
+impl Distribution for TestDistribution {
+    fn kind(&self) -> DistributionKind {
+        DistributionKind::Unknown
+    }
+
+    fn supports_format(&self, format: Format) -> bool {
+        self.formats.contains(&format)
+    }
+
+    #[boxed]
+    async fn load(&self) -> Result<(), LoadError> {
+        Ok(())
+    }
+
+    #[boxed]
+    async fn resolver(&self) -> Arc<Resolver> {
+        Arc::clone(&self.resolver)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct FeatureSpecFile {
     name: &'static str,
-    text: &'static str,
+    text: String,
 }
 
 pub struct FeatureSpec {
@@ -151,8 +192,39 @@ impl Default for FeatureSpec {
 }
 
 impl FeatureSpec {
-    pub fn file(name: &'static str, text: &'static str) -> FeatureSpecFile {
-        FeatureSpecFile { name, text }
+    pub fn file<T: Into<String>>(name: &'static str, text: T) -> FeatureSpecFile {
+        FeatureSpecFile {
+            name,
+            text: text.into(),
+        }
+    }
+
+    pub fn snippet_options() -> Options {
+        Options {
+            latex: Some(LatexOptions {
+                completion: Some(LatexCompletionOptions {
+                    argument_snippets: Some(true),
+                }),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        }
+    }
+
+    pub fn snippet_capabilities() -> ClientCapabilities {
+        ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                completion: Some(CompletionCapability {
+                    completion_item: Some(CompletionItemCapability {
+                        snippet_support: Some(true),
+                        ..CompletionItemCapability::default()
+                    }),
+                    ..CompletionCapability::default()
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        }
     }
 
     pub fn uri(name: &str) -> Url {
@@ -168,7 +240,7 @@ impl FeatureSpec {
     fn view(&self) -> DocumentView {
         let mut builder = TestWorkspaceBuilder::new();
         for file in &self.files {
-            builder.add_document(file.name, file.text);
+            builder.add_document(file.name, &file.text);
         }
         let workspace = builder.workspace;
         let main_uri = Self::uri(self.main_file);
@@ -253,6 +325,20 @@ impl Into<FeatureRequest<RenameParams>> for FeatureSpec {
     }
 }
 
+impl Into<FeatureRequest<CodeActionParams>> for FeatureSpec {
+    fn into(self) -> FeatureRequest<CodeActionParams> {
+        let params = CodeActionParams {
+            text_document: self.identifier(),
+            range: Range::new(self.position, self.position),
+            context: CodeActionContext {
+                diagnostics: Vec::new(),
+                only: None,
+            },
+        };
+        self.request(params)
+    }
+}
+
 impl Into<FeatureRequest<DocumentSymbolParams>> for FeatureSpec {
     fn into(self) -> FeatureRequest<DocumentSymbolParams> {
         let params = DocumentSymbolParams {
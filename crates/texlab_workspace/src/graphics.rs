@@ -0,0 +1,130 @@
+use super::workspace::Workspace;
+use path_clean::PathClean;
+use std::path::PathBuf;
+use texlab_protocol::{Options, Uri};
+use texlab_syntax::*;
+
+pub fn graphics_directories(workspace: &Workspace, uri: &Uri, options: &Options) -> Vec<PathBuf> {
+    let mut directories = Vec::new();
+    let base_path = match base_path(workspace, uri, options) {
+        Some(base_path) => base_path,
+        None => return directories,
+    };
+
+    for document in workspace.related_documents(uri, options) {
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            for path in tree
+                .graphics_paths
+                .iter()
+                .flat_map(LatexGraphicsPath::paths)
+            {
+                let directory = base_path.join(path.replace('\\', "/")).clean();
+                if !directories.contains(&directory) {
+                    directories.push(directory);
+                }
+            }
+        }
+    }
+    directories
+}
+
+pub fn graphics_targets(include: &LatexInclude, directories: &[PathBuf]) -> Vec<Vec<Uri>> {
+    let mut all_targets = include.all_targets.clone();
+    if !include.kind.is_graphics() {
+        return all_targets;
+    }
+
+    for (path, targets) in include.paths().into_iter().zip(&mut all_targets) {
+        for directory in directories {
+            let path = directory.join(path.text()).clean();
+            let path = match path.to_str() {
+                Some(path) => path.to_owned(),
+                None => continue,
+            };
+
+            targets.extend(Uri::from_file_path(&path).ok());
+            if let Some(extensions) = include.kind.extensions() {
+                for extension in extensions {
+                    let path = format!("{}.{}", &path, extension);
+                    targets.extend(Uri::from_file_path(&path).ok());
+                }
+            }
+        }
+    }
+    all_targets
+}
+
+fn base_path(workspace: &Workspace, uri: &Uri, options: &Options) -> Option<PathBuf> {
+    options
+        .latex
+        .as_ref()
+        .and_then(|opts| opts.root_directory.clone())
+        .or_else(|| {
+            let mut path = workspace
+                .find_parent(uri, options)
+                .map_or_else(|| uri.to_file_path(), |parent| parent.uri.to_file_path())
+                .ok()?;
+            path.pop();
+            Some(path)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::TestWorkspaceBuilder;
+    use std::env;
+
+    #[test]
+    fn directories_of_related_documents() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document(
+            "foo.tex",
+            "\\graphicspath{{figures/}{../img/}}\n\\include{bar}",
+        );
+        builder.add_document("bar.tex", "\\graphicspath{{figures/}}");
+        builder.add_document("baz.tex", "\\graphicspath{{baz/}}");
+        let directories = graphics_directories(&builder.workspace, &uri, &Options::default());
+
+        let mut base_path = uri.to_file_path().unwrap();
+        base_path.pop();
+        assert_eq!(
+            directories,
+            vec![
+                base_path.join("figures"),
+                base_path.parent().unwrap().join("img"),
+            ]
+        );
+    }
+
+    #[test]
+    fn directories_relative_to_parent() {
+        let mut builder = TestWorkspaceBuilder::new();
+        builder.add_document(
+            "foo.tex",
+            "\\begin{document}\\include{chapters/bar}\\end{document}",
+        );
+        let uri = builder.add_document("chapters/bar.tex", "\\graphicspath{{figures/}}");
+        let directories = graphics_directories(&builder.workspace, &uri, &Options::default());
+        assert_eq!(directories, vec![env::temp_dir().join("figures")]);
+    }
+
+    #[test]
+    fn targets_inside_directories() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", "\\includegraphics{bar}\n\\input{baz}");
+        let document = builder.workspace.find(&uri).unwrap();
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => unreachable!(),
+        };
+
+        let directory = env::temp_dir().join("figures");
+        let targets = graphics_targets(&tree.includes[0], &[directory.clone()]);
+        let target = Uri::from_file_path(directory.join("bar.png")).unwrap();
+        assert!(targets[0].contains(&target));
+
+        let targets = graphics_targets(&tree.includes[1], &[directory]);
+        assert_eq!(targets, tree.includes[1].all_targets);
+    }
+}
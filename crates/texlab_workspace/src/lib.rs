@@ -1,11 +1,17 @@
+mod cache;
 mod components;
 mod document;
 mod feature;
+mod graphics;
 mod outline;
+mod project;
 mod workspace;
 
+pub use self::cache::DocumentCache;
 pub use self::components::*;
 pub use self::document::Document;
 pub use self::feature::*;
+pub use self::graphics::*;
 pub use self::outline::*;
+pub use self::project::*;
 pub use self::workspace::*;
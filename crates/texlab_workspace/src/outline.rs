@@ -141,7 +141,7 @@ impl OutlineCaptionKind {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum OutlineContextItem {
     Section {
-        prefix: &'static str,
+        prefix: String,
         text: String,
     },
     Caption {
@@ -391,7 +391,7 @@ impl OutlineContext {
             range: section.range(),
             number: Self::find_number(view, label),
             item: Section {
-                prefix: section.prefix,
+                prefix: section.prefix.clone(),
                 text: extract_group(content),
             },
         })
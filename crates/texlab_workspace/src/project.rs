@@ -0,0 +1,68 @@
+use path_clean::PathClean;
+use std::path::{Path, PathBuf};
+use texlab_protocol::Uri;
+
+pub const PROJECT_FILE_NAME: &str = ".texlabroot";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Project {
+    pub directory: PathBuf,
+    pub roots: Vec<Uri>,
+}
+
+impl Project {
+    pub fn parse(path: &Path, text: &str) -> Option<Self> {
+        let directory = path.parent()?.to_owned();
+        let roots = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let path = directory.join(line.replace('\\', "/")).clean();
+                Uri::from_file_path(path).ok()
+            })
+            .collect();
+
+        Some(Self { directory, roots })
+    }
+
+    pub fn contains(&self, uri: &Uri) -> bool {
+        uri.to_file_path()
+            .map(|path| path.starts_with(&self.directory))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn parse() {
+        let directory = env::temp_dir();
+        let project = Project::parse(
+            &directory.join(PROJECT_FILE_NAME),
+            "# theses\nfoo/main.tex\n\n  bar/main.tex  \n",
+        )
+        .unwrap();
+        assert_eq!(project.directory, directory);
+        assert_eq!(
+            project.roots,
+            vec![
+                Uri::from_file_path(directory.join("foo/main.tex")).unwrap(),
+                Uri::from_file_path(directory.join("bar/main.tex")).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let directory = env::temp_dir().join("foo");
+        let project = Project::parse(&directory.join(PROJECT_FILE_NAME), "main.tex").unwrap();
+        let inside = Uri::from_file_path(directory.join("chapters/bar.tex")).unwrap();
+        let outside = Uri::from_file_path(env::temp_dir().join("bar.tex")).unwrap();
+        assert!(project.contains(&inside));
+        assert!(!project.contains(&outside));
+    }
+}
@@ -1,79 +1,360 @@
 use super::components::COMPONENT_DATABASE;
 use super::document::Document;
+use super::project::{Project, PROJECT_FILE_NAME};
 use path_clean::PathClean;
-use petgraph::visit::Dfs;
-use petgraph::Graph;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use texlab_distro::{Language, Resolver};
 use texlab_protocol::*;
 use texlab_syntax::*;
 
-#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[derive(Debug, Default)]
+struct QueryCache {
+    related_documents: HashMap<usize, Vec<Arc<Document>>>,
+    parents: HashMap<usize, Option<Arc<Document>>>,
+    components: Option<Vec<Vec<Arc<Document>>>>,
+}
+
+#[derive(Debug, Default)]
 pub struct Workspace {
-    pub documents: Vec<Arc<Document>>,
+    documents: Vec<Arc<Document>>,
+    indices_by_uri: HashMap<Uri, usize>,
+    targets: Vec<Vec<Uri>>,
+    dependents: HashMap<Uri, HashSet<usize>>,
+    children: Vec<Vec<usize>>,
+    parents: Vec<HashSet<usize>>,
+    projects: Vec<Project>,
+    options: Options,
+    project_options: HashMap<PathBuf, Options>,
+    cache: Mutex<QueryCache>,
 }
 
-impl Workspace {
-    pub fn new() -> Self {
+impl Clone for Workspace {
+    fn clone(&self) -> Self {
         Self {
-            documents: Vec::new(),
+            documents: self.documents.clone(),
+            indices_by_uri: self.indices_by_uri.clone(),
+            targets: self.targets.clone(),
+            dependents: self.dependents.clone(),
+            children: self.children.clone(),
+            parents: self.parents.clone(),
+            projects: self.projects.clone(),
+            options: self.options.clone(),
+            project_options: self.project_options.clone(),
+            cache: Mutex::default(),
         }
     }
+}
+
+impl PartialEq for Workspace {
+    fn eq(&self, other: &Self) -> bool {
+        self.documents == other.documents && self.projects == other.projects
+    }
+}
+
+impl Eq for Workspace {}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn documents(&self) -> &[Arc<Document>] {
+        &self.documents
+    }
+
+    pub fn projects(&self) -> &[Project] {
+        &self.projects
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    pub fn project_options(&self) -> &HashMap<PathBuf, Options> {
+        &self.project_options
+    }
 
     pub fn find(&self, uri: &Uri) -> Option<Arc<Document>> {
-        self.documents
-            .iter()
-            .find(|document| &document.uri == uri)
-            .map(|document| Arc::clone(&document))
+        self.indices_by_uri
+            .get(uri)
+            .map(|index| Arc::clone(&self.documents[*index]))
     }
 
-    pub fn related_documents(&self, uri: &Uri, options: &Options) -> Vec<Arc<Document>> {
-        let mut graph = Graph::new_undirected();
-        let mut indices_by_uri = HashMap::new();
-        for document in &self.documents {
-            indices_by_uri.insert(&document.uri, graph.add_node(document));
+    pub fn set_options(&mut self, options: &Options, project_options: HashMap<PathBuf, Options>) {
+        if &self.options == options && self.project_options == project_options {
+            return;
         }
 
-        for parent in self.documents.iter().filter(|doc| doc.is_file()) {
-            if let SyntaxTree::Latex(tree) = &parent.tree {
-                for include in &tree.includes {
-                    for targets in &include.all_targets {
-                        for target in targets {
-                            if let Some(ref child) = self.find(target) {
-                                graph.add_edge(
-                                    indices_by_uri[&parent.uri],
-                                    indices_by_uri[&child.uri],
-                                    (),
-                                );
-                            }
-                        }
-                    }
+        self.options = options.clone();
+        self.project_options = project_options;
+        self.update_all_targets();
+    }
+
+    fn update_all_targets(&mut self) {
+        self.cache = Mutex::default();
+        for index in 0..self.documents.len() {
+            self.update_targets(index);
+        }
+    }
+
+    fn document_options(&self, uri: &Uri) -> &Options {
+        self.find_project(uri)
+            .and_then(|project| self.project_options.get(&project.directory))
+            .unwrap_or(&self.options)
+    }
+
+    fn has_options(&self, options: &Options) -> bool {
+        &self.options == options || self.project_options.values().any(|other| other == options)
+    }
+
+    pub fn add(&mut self, document: Arc<Document>) {
+        self.cache = Mutex::default();
+        let index = match self.indices_by_uri.get(&document.uri) {
+            Some(index) => {
+                self.documents[*index] = document;
+                *index
+            }
+            None => {
+                let index = self.documents.len();
+                self.indices_by_uri.insert(document.uri.clone(), index);
+                self.documents.push(document);
+                self.targets.push(Vec::new());
+                self.children.push(Vec::new());
+                self.parents.push(HashSet::new());
+
+                let uri = &self.documents[index].uri;
+                let dependents: Vec<_> = self
+                    .dependents
+                    .get(uri)
+                    .map(|dependents| dependents.iter().copied().collect())
+                    .unwrap_or_default();
+
+                for dependent in dependents {
+                    self.update_children(dependent);
+                }
+                index
+            }
+        };
+        self.update_targets(index);
+    }
+
+    pub fn add_project(&mut self, project: Project) {
+        self.cache = Mutex::default();
+        self.projects
+            .retain(|other| other.directory != project.directory);
+        self.projects.push(project);
+    }
+
+    pub fn remove_project(&mut self, directory: &Path) {
+        self.cache = Mutex::default();
+        self.projects
+            .retain(|project| project.directory != directory);
+        if self.project_options.remove(directory).is_some() {
+            self.update_all_targets();
+        }
+    }
+
+    fn update_targets(&mut self, index: usize) {
+        for target in &self.targets[index] {
+            if let Some(dependents) = self.dependents.get_mut(target) {
+                dependents.remove(&index);
+            }
+        }
+
+        let document = &self.documents[index];
+        let targets = self.collect_targets(document, self.document_options(&document.uri));
+        for target in &targets {
+            self.dependents
+                .entry(target.clone())
+                .or_default()
+                .insert(index);
+        }
+        self.targets[index] = targets;
+        self.update_children(index);
+    }
+
+    fn collect_targets(&self, document: &Document, options: &Options) -> Vec<Uri> {
+        let mut targets = Vec::new();
+        if !document.is_file() {
+            return targets;
+        }
+
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            for include in &tree.includes {
+                for include_targets in &include.all_targets {
+                    targets.extend(include_targets.iter().cloned());
+                }
+            }
+
+            if let Some(aux_uri) = Self::aux_path(&document.uri, options)
+                .and_then(|aux_path| Uri::from_file_path(aux_path).ok())
+            {
+                targets.push(aux_uri);
+            }
+
+            if let Some(root) = &tree.magic.root {
+                targets.push(root.clone());
+            }
+        }
+        targets
+    }
+
+    fn update_children(&mut self, index: usize) {
+        for child in &self.children[index] {
+            self.parents[*child].remove(&index);
+        }
+
+        let mut children = Vec::new();
+        for target in &self.targets[index] {
+            if let Some(child) = self.indices_by_uri.get(target) {
+                if !children.contains(child) {
+                    children.push(*child);
                 }
+            }
+        }
 
-                if let Some(child) = Self::aux_path(&parent.uri, options)
-                    .and_then(|aux_path| Uri::from_file_path(aux_path).ok())
-                    .and_then(|aux_uri| self.find(&aux_uri))
-                {
-                    graph.add_edge(indices_by_uri[&parent.uri], indices_by_uri[&child.uri], ());
+        for child in &children {
+            self.parents[*child].insert(index);
+        }
+        self.children[index] = children;
+    }
+
+    fn graph(&self, options: &Options) -> (Vec<Vec<usize>>, Vec<HashSet<usize>>) {
+        let mut children = vec![Vec::new(); self.documents.len()];
+        let mut parents = vec![HashSet::new(); self.documents.len()];
+        for (index, document) in self.documents.iter().enumerate() {
+            for target in self.collect_targets(document, options) {
+                if let Some(child) = self.indices_by_uri.get(&target) {
+                    if !children[index].contains(child) {
+                        children[index].push(*child);
+                        parents[*child].insert(index);
+                    }
                 }
             }
         }
+        (children, parents)
+    }
 
+    fn traverse(
+        &self,
+        start: usize,
+        children: &[Vec<usize>],
+        parents: &[HashSet<usize>],
+    ) -> Vec<Arc<Document>> {
         let mut documents = Vec::new();
-        if self.find(uri).is_some() {
-            let mut dfs = Dfs::new(&graph, indices_by_uri[uri]);
-            while let Some(index) = dfs.next(&graph) {
-                documents.push(Arc::clone(&graph.node_weight(index).unwrap()));
+        let mut visited = vec![false; self.documents.len()];
+        let mut stack = vec![start];
+        while let Some(index) = stack.pop() {
+            if visited[index] {
+                continue;
+            }
+            visited[index] = true;
+
+            for child in children[index].iter().rev() {
+                if !visited[*child] {
+                    stack.push(*child);
+                }
             }
+
+            let mut neighbors: Vec<_> = parents[index].iter().copied().collect();
+            neighbors.sort_by(|a, b| b.cmp(a));
+            for parent in neighbors {
+                if !visited[parent] {
+                    stack.push(parent);
+                }
+            }
+
+            documents.push(Arc::clone(&self.documents[index]));
         }
         documents
     }
 
+    pub fn related_documents(&self, uri: &Uri, options: &Options) -> Vec<Arc<Document>> {
+        let index = match self.indices_by_uri.get(uri) {
+            Some(index) => *index,
+            None => return Vec::new(),
+        };
+
+        if !self.has_options(options) {
+            let (children, parents) = self.graph(options);
+            return self.traverse(index, &children, &parents);
+        }
+
+        let mut cache = self.cache.lock().unwrap();
+        cache
+            .related_documents
+            .entry(index)
+            .or_insert_with(|| self.traverse(index, &self.children, &self.parents))
+            .clone()
+    }
+
+    pub fn components(&self, options: &Options) -> Vec<Vec<Arc<Document>>> {
+        if !self.has_options(options) {
+            let (children, parents) = self.graph(options);
+            return self.collect_components(&children, &parents);
+        }
+
+        let mut cache = self.cache.lock().unwrap();
+        cache
+            .components
+            .get_or_insert_with(|| self.collect_components(&self.children, &self.parents))
+            .clone()
+    }
+
+    fn collect_components(
+        &self,
+        children: &[Vec<usize>],
+        parents: &[HashSet<usize>],
+    ) -> Vec<Vec<Arc<Document>>> {
+        let mut components = Vec::new();
+        let mut visited = vec![false; self.documents.len()];
+        for index in 0..self.documents.len() {
+            if visited[index] {
+                continue;
+            }
+
+            let component = self.traverse(index, children, parents);
+            for document in &component {
+                visited[self.indices_by_uri[&document.uri]] = true;
+            }
+            components.push(component);
+        }
+        components
+    }
+
     pub fn find_parent(&self, uri: &Uri, options: &Options) -> Option<Arc<Document>> {
-        for document in self.related_documents(uri, options) {
+        let index = *self.indices_by_uri.get(uri)?;
+        if self.has_options(options) {
+            if let Some(parent) = self.cache.lock().unwrap().parents.get(&index) {
+                return parent.clone();
+            }
+        }
+
+        let parent = self.compute_parent(uri, options);
+        if self.has_options(options) {
+            self.cache
+                .lock()
+                .unwrap()
+                .parents
+                .insert(index, parent.clone());
+        }
+        parent
+    }
+
+    fn compute_parent(&self, uri: &Uri, options: &Options) -> Option<Arc<Document>> {
+        let related_documents = self.related_documents(uri, options);
+        if let Some(root) = self.find_magic_root(uri) {
+            return Some(root);
+        }
+
+        if let Some(root) = self.find_project_root(uri, &related_documents) {
+            return Some(root);
+        }
+
+        for document in related_documents {
             if let SyntaxTree::Latex(tree) = &document.tree {
                 if tree.env.is_standalone {
                     return Some(document);
@@ -83,6 +364,36 @@ impl Workspace {
         None
     }
 
+    pub fn find_project(&self, uri: &Uri) -> Option<&Project> {
+        self.projects
+            .iter()
+            .filter(|project| project.contains(uri))
+            .max_by_key(|project| project.directory.components().count())
+    }
+
+    fn find_magic_root(&self, uri: &Uri) -> Option<Arc<Document>> {
+        let document = self.find(uri)?;
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            tree.magic.root.as_ref().and_then(|root| self.find(root))
+        } else {
+            None
+        }
+    }
+
+    fn find_project_root(
+        &self,
+        uri: &Uri,
+        related_documents: &[Arc<Document>],
+    ) -> Option<Arc<Document>> {
+        let project = self.find_project(uri)?;
+        project
+            .roots
+            .iter()
+            .find(|root| related_documents.iter().any(|doc| &doc.uri == *root))
+            .or_else(|| project.roots.iter().find(|root| self.find(root).is_some()))
+            .and_then(|root| self.find(root))
+    }
+
     pub fn unresolved_includes(&self, options: &Options) -> Vec<PathBuf> {
         let mut includes = Vec::new();
         for document in &self.documents {
@@ -120,6 +431,16 @@ impl Workspace {
                     }
                 }
 
+                if let Some(root) = &tree.magic.root {
+                    if self.find(root).is_none() {
+                        if let Ok(path) = root.to_file_path() {
+                            if path.exists() {
+                                includes.push(path);
+                            }
+                        }
+                    }
+                }
+
                 if let Some(aux_path) = Self::aux_path(&document.uri, options) {
                     if self
                         .find(&Uri::from_file_path(&aux_path).unwrap())
@@ -130,6 +451,18 @@ impl Workspace {
                 }
             }
         }
+
+        for project in &self.projects {
+            for root in &project.roots {
+                if self.find(root).is_none() {
+                    if let Ok(path) = root.to_file_path() {
+                        if path.exists() {
+                            includes.push(path);
+                        }
+                    }
+                }
+            }
+        }
         includes
     }
 
@@ -163,9 +496,15 @@ impl TestWorkspaceBuilder {
         let language = Language::by_extension(path.extension().unwrap().to_str().unwrap()).unwrap();
         let uri = Uri::from_file_path(path).unwrap();
         let document = Document::parse(uri.clone(), text.to_owned(), language, &options, &resolver);
-        self.workspace.documents.push(Arc::new(document));
+        self.workspace.add(Arc::new(document));
         uri
     }
+
+    pub fn add_project(&mut self, text: &str) {
+        let path = env::temp_dir().join(PROJECT_FILE_NAME);
+        let project = Project::parse(&path, text).unwrap();
+        self.workspace.add_project(project);
+    }
 }
 
 #[cfg(test)]
@@ -287,6 +626,127 @@ mod tests {
         verify_documents(vec![uri1, uri2], documents);
     }
 
+    #[test]
+    fn related_documents_aux_directory() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri1 = builder.add_document("foo.tex", "");
+        builder.add_document("out/foo.aux", "");
+        let uri2 = builder.add_document("out/aux/foo.aux", "");
+        let options = Options {
+            latex: Some(LatexOptions {
+                build: Some(LatexBuildOptions {
+                    output_directory: Some(PathBuf::from("out")),
+                    aux_directory: Some(PathBuf::from("out/aux")),
+                    ..LatexBuildOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        };
+        let documents = builder.workspace.related_documents(&uri1, &options);
+        verify_documents(vec![uri1, uri2], documents);
+    }
+
+    #[test]
+    fn related_documents_incremental_update() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri1 = builder.add_document("foo.tex", "\\include{bar}");
+        let uri2 = builder.add_document("bar.tex", "");
+        let documents = builder
+            .workspace
+            .related_documents(&uri2, &Options::default());
+        verify_documents(vec![uri2.clone(), uri1.clone()], documents);
+
+        builder.add_document("foo.tex", "");
+        let documents = builder
+            .workspace
+            .related_documents(&uri2, &Options::default());
+        verify_documents(vec![uri2], documents);
+        assert_eq!(builder.workspace.documents().len(), 2);
+        assert_eq!(builder.workspace.find(&uri1).unwrap().text, "");
+    }
+
+    #[test]
+    fn related_documents_set_options() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri1 = builder.add_document("foo.tex", "");
+        let uri2 = builder.add_document("out/foo.aux", "");
+        let options = Options {
+            latex: Some(LatexOptions {
+                build: Some(LatexBuildOptions {
+                    output_directory: Some(PathBuf::from("out")),
+                    ..LatexBuildOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        };
+        builder.workspace.set_options(&options, HashMap::new());
+        let documents = builder.workspace.related_documents(&uri1, &options);
+        verify_documents(vec![uri1, uri2], documents);
+    }
+
+    #[test]
+    fn related_documents_project_options() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri1 = builder.add_document("foo.tex", "");
+        let uri2 = builder.add_document("out/foo.aux", "");
+        builder.add_project("foo.tex");
+        let options = Options {
+            latex: Some(LatexOptions {
+                build: Some(LatexBuildOptions {
+                    output_directory: Some(PathBuf::from("out")),
+                    ..LatexBuildOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        };
+        let mut project_options = HashMap::new();
+        project_options.insert(env::temp_dir(), options.clone());
+        builder
+            .workspace
+            .set_options(&Options::default(), project_options);
+
+        let documents = builder.workspace.related_documents(&uri1, &options);
+        verify_documents(vec![uri1.clone(), uri2.clone()], documents);
+        assert!(
+            builder
+                .workspace
+                .cache
+                .lock()
+                .unwrap()
+                .related_documents
+                .len()
+                == 1
+        );
+
+        let documents = builder.workspace.related_documents(&uri1, &options);
+        verify_documents(vec![uri1, uri2], documents);
+        assert!(
+            builder
+                .workspace
+                .cache
+                .lock()
+                .unwrap()
+                .related_documents
+                .len()
+                == 1
+        );
+    }
+
+    #[test]
+    fn components() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri1 = builder.add_document("foo.tex", "\\include{bar}");
+        let uri2 = builder.add_document("bar.tex", "");
+        let uri3 = builder.add_document("baz.tex", "");
+        let components = builder.workspace.components(&Options::default());
+        assert_eq!(components.len(), 2);
+        verify_documents(vec![uri1, uri2], components[0].clone());
+        verify_documents(vec![uri3], components[1].clone());
+    }
+
     #[test]
     fn find_parent() {
         let mut builder = TestWorkspaceBuilder::new();
@@ -308,4 +768,67 @@ mod tests {
         let document = builder.workspace.find_parent(&uri, &Options::default());
         assert_eq!(None, document);
     }
+
+    #[test]
+    fn related_documents_magic_comment() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri1 = builder.add_document("foo.tex", "% !TEX root = bar.tex");
+        let uri2 = builder.add_document("bar.tex", "");
+        let documents = builder
+            .workspace
+            .related_documents(&uri2, &Options::default());
+        verify_documents(vec![uri2, uri1], documents);
+    }
+
+    #[test]
+    fn find_parent_magic_comment() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri1 = builder.add_document("foo.tex", "% !TEX root = baz.tex");
+        builder.add_document("bar.tex", "\\begin{document}\\include{foo}\\end{document}");
+        let uri2 = builder.add_document("baz.tex", "\\begin{document}\\end{document}");
+        let document = builder
+            .workspace
+            .find_parent(&uri1, &Options::default())
+            .unwrap();
+        assert_eq!(uri2, document.uri);
+    }
+
+    #[test]
+    fn find_parent_project() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri1 = builder.add_document("foo.tex", "");
+        let uri2 = builder.add_document("bar.tex", "\\begin{document}\\end{document}");
+        builder.add_project("bar.tex\nbaz.tex");
+        let document = builder
+            .workspace
+            .find_parent(&uri1, &Options::default())
+            .unwrap();
+        assert_eq!(uri2, document.uri);
+    }
+
+    #[test]
+    fn find_parent_project_shared() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri1 = builder.add_document("foo.tex", "");
+        builder.add_document("bar.tex", "\\begin{document}\\end{document}");
+        let uri2 =
+            builder.add_document("baz.tex", "\\begin{document}\\include{foo}\\end{document}");
+        builder.add_project("bar.tex\nbaz.tex");
+        let document = builder
+            .workspace
+            .find_parent(&uri1, &Options::default())
+            .unwrap();
+        assert_eq!(uri2, document.uri);
+    }
+
+    #[test]
+    fn find_parent_project_removed() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", "");
+        builder.add_document("bar.tex", "\\begin{document}\\end{document}");
+        builder.add_project("bar.tex");
+        builder.workspace.remove_project(&env::temp_dir());
+        let document = builder.workspace.find_parent(&uri, &Options::default());
+        assert_eq!(None, document);
+    }
 }
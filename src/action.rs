@@ -15,6 +15,7 @@ pub enum Action {
     LoadConfiguration,
     UpdateConfiguration(serde_json::Value),
     DetectRoot(Uri),
+    DetectChildren,
     PublishDiagnostics,
     RunLinter(Uri, LintReason),
     Build(Uri),
@@ -9,6 +9,7 @@ use std::path::Path;
 use std::process::Stdio;
 use std::sync::Arc;
 use texlab_protocol::*;
+use texlab_syntax::*;
 use texlab_workspace::*;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
@@ -33,7 +34,7 @@ where
         }
     }
 
-    async fn build<'a>(&'a self, path: &'a Path) -> io::Result<bool> {
+    async fn build<'a>(&'a self, path: &'a Path, mut args: Vec<String>) -> io::Result<bool> {
         let build_options = self
             .options
             .build
@@ -49,8 +50,6 @@ where
             .or_else(|| path.parent())
             .unwrap();
 
-        let mut args = Vec::new();
-        args.append(&mut build_options.args());
         args.push(path.to_string_lossy().into_owned());
 
         let mut process = Command::new(build_options.executable())
@@ -116,7 +115,22 @@ where
                     self.client.progress(params).await;
                 }
 
-                let status = match self.build(&path).await {
+                let build_options = self.options.build.clone().unwrap_or_default();
+                let magic = match &document.tree {
+                    SyntaxTree::Latex(tree) => Some(&tree.magic),
+                    SyntaxTree::Bibtex(_) => None,
+                };
+                let mut warnings = Vec::new();
+                let args = build_args(&build_options, magic, &mut warnings);
+                for message in warnings {
+                    let params = LogMessageParams {
+                        typ: MessageType::Warning,
+                        message,
+                    };
+                    self.client.log_message(params).await;
+                }
+
+                let status = match self.build(&path, args).await {
                     Ok(true) => BuildStatus::Success,
                     Ok(false) => BuildStatus::Error,
                     Err(_) => BuildStatus::Failure,
@@ -131,6 +145,83 @@ where
     }
 }
 
+const LATEXMK_ENGINE_FLAGS: &[(&str, &str)] = &[
+    ("latex", "-dvi"),
+    ("pdflatex", "-pdf"),
+    ("xelatex", "-pdfxe"),
+    ("lualatex", "-pdflua"),
+];
+
+const LATEXMK_OUTPUT_FLAGS: &[&str] = &[
+    "-dvi",
+    "-pdf",
+    "-pdfxe",
+    "-pdflua",
+    "-xelatex",
+    "-lualatex",
+    "-pdfdvi",
+    "-pdfps",
+    "-ps",
+];
+
+const LATEXMK_BIB_PROGRAMS: &[&str] = &["bibtex", "biber"];
+
+fn is_latexmk(executable: &str) -> bool {
+    Path::new(executable)
+        .file_stem()
+        .map_or(false, |stem| stem == "latexmk")
+}
+
+fn build_args(
+    options: &LatexBuildOptions,
+    magic: Option<&LatexMagicCommentInfo>,
+    warnings: &mut Vec<String>,
+) -> Vec<String> {
+    let mut args = options.args();
+    let magic = match magic {
+        Some(magic) => magic,
+        None => return args,
+    };
+    let is_latexmk = is_latexmk(&options.executable());
+
+    if let Some(program) = &magic.tex_program {
+        let flag = LATEXMK_ENGINE_FLAGS
+            .iter()
+            .find(|(name, _)| *name == program.to_lowercase())
+            .map(|(_, flag)| *flag);
+
+        match (options.engine_args(program), flag) {
+            (Some(mut engine_args), _) => args.append(&mut engine_args),
+            (None, Some(flag)) if is_latexmk => {
+                args.retain(|arg| !LATEXMK_OUTPUT_FLAGS.contains(&arg.as_str()));
+                args.insert(0, flag.to_owned());
+            }
+            (None, _) => warnings.push(format!(
+                "No build arguments configured for TeX program \"{}\" (latex.build.engineArgs)",
+                program
+            )),
+        }
+    }
+
+    if let Some(program) = &magic.bib_program {
+        match options.engine_args(program) {
+            Some(mut engine_args) => args.append(&mut engine_args),
+            None if is_latexmk
+                && LATEXMK_BIB_PROGRAMS.contains(&program.to_lowercase().as_str()) =>
+            {
+                if !args.iter().any(|arg| arg == "-bibtex") {
+                    args.insert(0, "-bibtex".to_owned());
+                }
+            }
+            None => warnings.push(format!(
+                "No build arguments configured for BibTeX program \"{}\" (latex.build.engineArgs)",
+                program
+            )),
+        }
+    }
+    args
+}
+
 pub struct BuildManager<C> {
     handles_by_token: Mutex<HashMap<ProgressToken, AbortHandle>>,
     client: Arc<C>,
@@ -195,3 +286,65 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn magic(tex_program: Option<&str>, bib_program: Option<&str>) -> LatexMagicCommentInfo {
+        LatexMagicCommentInfo {
+            tex_program: tex_program.map(Into::into),
+            bib_program: bib_program.map(Into::into),
+            ..LatexMagicCommentInfo::default()
+        }
+    }
+
+    fn args(options: &LatexBuildOptions, magic: &LatexMagicCommentInfo) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let args = build_args(options, Some(magic), &mut warnings);
+        assert!(warnings.is_empty());
+        args
+    }
+
+    #[test]
+    fn latexmk_engine() {
+        let options = LatexBuildOptions {
+            args: Some(vec!["-pdf".into(), "-outdir=out".into()]),
+            ..LatexBuildOptions::default()
+        };
+        assert_eq!(
+            args(&options, &magic(Some("XeLaTeX"), Some("biber"))),
+            vec!["-bibtex", "-pdfxe", "-outdir=out"]
+        );
+    }
+
+    #[test]
+    fn custom_executable() {
+        let options = LatexBuildOptions {
+            executable: Some("tectonic".into()),
+            args: Some(vec!["--synctex".into()]),
+            ..LatexBuildOptions::default()
+        };
+        let mut warnings = Vec::new();
+        assert_eq!(
+            build_args(&options, Some(&magic(Some("xelatex"), None)), &mut warnings),
+            vec!["--synctex"]
+        );
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn engine_args_table() {
+        let mut table = HashMap::new();
+        table.insert("lualatex".to_owned(), vec!["-lualatex".to_owned()]);
+        let options = LatexBuildOptions {
+            args: Some(vec!["-interaction=nonstopmode".into()]),
+            engine_args: Some(table),
+            ..LatexBuildOptions::default()
+        };
+        assert_eq!(
+            args(&options, &magic(Some("lualatex"), None)),
+            vec!["-interaction=nonstopmode", "-lualatex"]
+        );
+    }
+}
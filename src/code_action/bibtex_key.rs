@@ -0,0 +1,305 @@
+use crate::rename::rename_key;
+use futures_boxed::boxed;
+use texlab_citeproc::name::{self, Name};
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "an", "and", "at", "by", "for", "from", "in", "into", "of", "on", "the", "to",
+    "towards", "with",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BibtexKeyCodeActionProvider;
+
+impl FeatureProvider for BibtexKeyCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        Self::generate_action(request).into_iter().collect()
+    }
+}
+
+impl BibtexKeyCodeActionProvider {
+    fn generate_action(request: &FeatureRequest<CodeActionParams>) -> Option<CodeAction> {
+        let tree = match &request.document().tree {
+            SyntaxTree::Bibtex(tree) => tree,
+            SyntaxTree::Latex(_) => return None,
+        };
+
+        let position = request.params.range.start;
+        let entry = tree.entries().into_iter().find(|entry| {
+            !entry.is_comment()
+                && entry
+                    .key
+                    .as_ref()
+                    .map_or(false, |key| key.range().contains(position))
+        })?;
+        let old_key = entry.key.as_ref()?.text();
+
+        let template = request
+            .options
+            .bibtex
+            .clone()
+            .unwrap_or_default()
+            .key_template();
+
+        let new_key = Self::generate_key(&template, entry);
+        if new_key.is_empty() {
+            return None;
+        }
+
+        let new_key = Self::disambiguate(new_key, &Self::existing_keys(request, old_key));
+        if new_key == old_key {
+            return None;
+        }
+
+        Some(CodeAction {
+            title: format!("Generate citation key \"{}\"", new_key),
+            kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+            diagnostics: None,
+            edit: Some(rename_key(request.related_documents(), old_key, &new_key)),
+            command: None,
+        })
+    }
+
+    fn generate_key(template: &str, entry: &BibtexEntry) -> String {
+        let mut key = String::new();
+        let mut chars = template.chars();
+        while let Some(c) = chars.next() {
+            if c == '{' {
+                let placeholder: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                key.push_str(&Self::expand(&placeholder, entry).unwrap_or_default());
+            } else {
+                key.push(c);
+            }
+        }
+        key
+    }
+
+    fn expand(placeholder: &str, entry: &BibtexEntry) -> Option<String> {
+        match placeholder {
+            "auth" => Self::last_names(entry).into_iter().next(),
+            "authors" => {
+                let names = Self::last_names(entry);
+                if names.len() > 2 {
+                    Some(format!("{}EtAl", names[0]))
+                } else {
+                    Some(names.concat())
+                }
+            }
+            "year" => Self::field_text(entry, "year")
+                .or_else(|| Self::field_text(entry, "date"))
+                .map(|date| date.chars().filter(char::is_ascii_digit).take(4).collect()),
+            "shorttitle" => Self::title_words(entry).into_iter().next(),
+            "title" => Some(Self::title_words(entry).into_iter().take(3).collect()),
+            _ => None,
+        }
+    }
+
+    fn last_names(entry: &BibtexEntry) -> Vec<String> {
+        let names = Self::field_text(entry, "author")
+            .or_else(|| Self::field_text(entry, "editor"))
+            .unwrap_or_default();
+
+        name::parse(&names)
+            .into_iter()
+            .filter_map(|name| match name {
+                Name::Person(person) => person.family,
+                Name::Literal { literal } => Some(literal),
+            })
+            .map(|name| Self::sanitize(&name))
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
+    fn title_words(entry: &BibtexEntry) -> Vec<String> {
+        Self::field_text(entry, "title")
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(Self::sanitize)
+            .filter(|word| !word.is_empty())
+            .filter(|word| !STOP_WORDS.contains(&word.to_lowercase().as_str()))
+            .map(|word| titlelize(&word))
+            .collect()
+    }
+
+    fn field_text(entry: &BibtexEntry, name: &str) -> Option<String> {
+        entry.field(name)?.content.as_ref().map(BibtexContent::text)
+    }
+
+    fn sanitize(text: &str) -> String {
+        text.chars().filter(|c| c.is_alphanumeric()).collect()
+    }
+
+    fn existing_keys<'a>(
+        request: &'a FeatureRequest<CodeActionParams>,
+        old_key: &str,
+    ) -> Vec<&'a str> {
+        let mut keys = Vec::new();
+        for document in request.related_documents() {
+            if let SyntaxTree::Bibtex(tree) = &document.tree {
+                tree.entries()
+                    .into_iter()
+                    .filter(|entry| !entry.is_comment())
+                    .filter_map(|entry| entry.key.as_ref())
+                    .map(BibtexToken::text)
+                    .filter(|key| *key != old_key)
+                    .for_each(|key| keys.push(key));
+            }
+        }
+        keys
+    }
+
+    fn disambiguate(key: String, existing_keys: &[&str]) -> String {
+        if !existing_keys.contains(&key.as_str()) {
+            return key;
+        }
+
+        (b'a'..=b'z')
+            .map(|c| format!("{}{}", key, c as char))
+            .chain((2..).map(|i| format!("{}{}", key, i)))
+            .find(|candidate| !existing_keys.contains(&candidate.as_str()))
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn generate() {
+        let actions = test_feature(
+            BibtexKeyCodeActionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.bib",
+                        "@article{S0021, author = {Foo Bar and Baz Qux}, year = 2020, \
+                         title = {The Python Language}}",
+                    ),
+                    FeatureSpec::file("bar.tex", "\\addbibresource{foo.bib}\n\\cite{S0021}"),
+                ],
+                main_file: "foo.bib",
+                position: Position::new(0, 11),
+                ..FeatureSpec::default()
+            },
+        );
+        let mut changes = HashMap::new();
+        changes.insert(
+            FeatureSpec::uri("foo.bib"),
+            vec![TextEdit::new(
+                Range::new_simple(0, 9, 0, 14),
+                "Bar2020Python".into(),
+            )],
+        );
+        changes.insert(
+            FeatureSpec::uri("bar.tex"),
+            vec![TextEdit::new(
+                Range::new_simple(1, 6, 1, 11),
+                "Bar2020Python".into(),
+            )],
+        );
+        assert_eq!(
+            actions,
+            vec![CodeAction {
+                title: "Generate citation key \"Bar2020Python\"".into(),
+                kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+                diagnostics: None,
+                edit: Some(WorkspaceEdit::new(changes)),
+                command: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn disambiguate() {
+        let actions = test_feature(
+            BibtexKeyCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@article{Bar2020Python,}\n\
+                     @article{S0021, author = {Bar, Foo}, date = {2020-01-01}, \
+                     title = {Python}}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(1, 11),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Generate citation key \"Bar2020Pythona\"");
+    }
+
+    #[test]
+    fn custom_template() {
+        let actions = test_feature(
+            BibtexKeyCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@article{S0021, author = {Foo Bar and Baz Qux and Quux Corge}, \
+                     year = 2020, title = {On the Art of Computer Programming}}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 11),
+                options: Options {
+                    bibtex: Some(BibtexOptions {
+                        key_template: Some("{authors}:{year}:{title}".into()),
+                        ..BibtexOptions::default()
+                    }),
+                    ..Options::default()
+                },
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions[0].title,
+            "Generate citation key \"BarEtAl:2020:ArtComputerProgramming\""
+        );
+    }
+
+    #[test]
+    fn same_key() {
+        let actions = test_feature(
+            BibtexKeyCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@article{Bar2020Python, author = {Foo Bar}, year = 2020, title = {Python}}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 11),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(actions, Vec::new());
+    }
+
+    #[test]
+    fn outside_key() {
+        let actions = test_feature(
+            BibtexKeyCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@article{S0021, author = {Foo Bar}, year = 2020, title = {Python}}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 3),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(actions, Vec::new());
+    }
+}
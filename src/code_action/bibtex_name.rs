@@ -0,0 +1,210 @@
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_citeproc::name::{self, Name, PersonName};
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BibtexNameCodeActionProvider;
+
+impl FeatureProvider for BibtexNameCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        Self::normalize_action(request).into_iter().collect()
+    }
+}
+
+impl BibtexNameCodeActionProvider {
+    fn normalize_action(request: &FeatureRequest<CodeActionParams>) -> Option<CodeAction> {
+        let tree = match &request.document().tree {
+            SyntaxTree::Bibtex(tree) => tree,
+            SyntaxTree::Latex(_) => return None,
+        };
+
+        let field =
+            tree.find(request.params.range.start)
+                .into_iter()
+                .find_map(|node| match node {
+                    BibtexNode::Field(field) => Some(field),
+                    _ => None,
+                })?;
+
+        let field_name = field.name.text().to_lowercase();
+        if !BIBTEX_NAME_FIELDS.contains(&field_name.as_str()) {
+            return None;
+        }
+
+        let content = field.content.as_ref()?;
+        let list = BibtexNameList::parse(content)?;
+        let text = &request.document().text;
+        let names: Vec<String> = list
+            .names
+            .iter()
+            .flat_map(|name| {
+                if name.is_comma_separated_list() {
+                    name.segments()
+                } else {
+                    vec![name.clone()]
+                }
+            })
+            .filter_map(|name| Self::normalize_name(text, &name))
+            .collect();
+
+        let (left, right) = match content {
+            BibtexContent::QuotedContent(_) => ('"', '"'),
+            _ => ('{', '}'),
+        };
+        let new_text = format!("{}{}{}", left, names.join(" and "), right);
+        if new_text == CharStream::extract(text, content.range()) {
+            return None;
+        }
+
+        let mut changes = HashMap::new();
+        changes.insert(
+            request.document().uri.clone().into(),
+            vec![TextEdit::new(content.range(), new_text)],
+        );
+
+        Some(CodeAction {
+            title: "Normalize name list".into(),
+            kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+            diagnostics: None,
+            edit: Some(WorkspaceEdit::new(changes)),
+            command: None,
+        })
+    }
+
+    fn normalize_name(text: &str, name: &BibtexName) -> Option<String> {
+        let source = CharStream::extract(text, name.range()?);
+        if let [BibtexContent::BracedContent(_)] = name.parts.as_slice() {
+            return Some(source);
+        }
+
+        let mut names = name::parse(&source);
+        match (names.pop(), names.is_empty()) {
+            (Some(Name::Person(person)), true) => Some(Self::format_person(&person)),
+            _ => Some(source),
+        }
+    }
+
+    fn format_person(person: &PersonName) -> String {
+        let last = match &person.non_dropping_particle {
+            Some(particle) => format!(
+                "{} {}",
+                particle,
+                person
+                    .family
+                    .as_ref()
+                    .map(String::as_str)
+                    .unwrap_or_default()
+            ),
+            None => person.family.clone().unwrap_or_default(),
+        };
+
+        let mut parts = vec![last];
+        parts.extend(person.suffix.iter().cloned());
+        parts.extend(person.given.iter().cloned());
+        parts
+            .into_iter()
+            .map(|part| part.trim().to_owned())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verify(text: &'static str, expected: Option<&str>) {
+        let actions = test_feature(
+            BibtexNameCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", text)],
+                main_file: "foo.bib",
+                position: Position::new(0, 26),
+                ..FeatureSpec::default()
+            },
+        );
+
+        let expected = expected.map(|new_text| {
+            let tree = BibtexSyntaxTree::from(text);
+            let range = tree.entries()[0].fields[0]
+                .content
+                .as_ref()
+                .unwrap()
+                .range();
+            let mut changes = HashMap::new();
+            changes.insert(
+                FeatureSpec::uri("foo.bib"),
+                vec![TextEdit::new(range, new_text.into())],
+            );
+            CodeAction {
+                title: "Normalize name list".into(),
+                kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+                diagnostics: None,
+                edit: Some(WorkspaceEdit::new(changes)),
+                command: None,
+            }
+        });
+        assert_eq!(actions, expected.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn given_family() {
+        verify(
+            "@article{foo, author = {Michael van Gerwen and Ken Thompson}}",
+            Some("{van Gerwen, Michael and Thompson, Ken}"),
+        );
+    }
+
+    #[test]
+    fn comma_separator() {
+        verify(
+            "@article{foo, author = \"Foo Bar, Baz Qux, Quux Corge\"}",
+            Some("\"Bar, Foo and Qux, Baz and Corge, Quux\""),
+        );
+    }
+
+    #[test]
+    fn trailing_and() {
+        verify(
+            "@article{foo, editor = {Friedman, Jr., George and}}",
+            Some("{Friedman, Jr., George}"),
+        );
+    }
+
+    #[test]
+    fn corporate_name() {
+        verify(
+            "@article{foo, author = {{Barnes and Noble} and Ken Thompson}}",
+            Some("{{Barnes and Noble} and Thompson, Ken}"),
+        );
+    }
+
+    #[test]
+    fn multi_word_family() {
+        verify(
+            "@article{foo, author = {García Márquez, Gabriel José and Ken Thompson}}",
+            Some("{García Márquez, Gabriel José and Thompson, Ken}"),
+        );
+    }
+
+    #[test]
+    fn already_normalized() {
+        verify("@article{foo, author = {Thompson, Ken}}", None);
+    }
+
+    #[test]
+    fn other_field() {
+        verify("@article{foo, title = {Ken Thompson and Foo}}", None);
+    }
+}
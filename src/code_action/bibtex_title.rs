@@ -0,0 +1,108 @@
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BibtexTitleCodeActionProvider;
+
+impl FeatureProvider for BibtexTitleCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        Self::protect_action(request).into_iter().collect()
+    }
+}
+
+impl BibtexTitleCodeActionProvider {
+    fn protect_action(request: &FeatureRequest<CodeActionParams>) -> Option<CodeAction> {
+        let tree = match &request.document().tree {
+            SyntaxTree::Bibtex(tree) => tree,
+            SyntaxTree::Latex(_) => return None,
+        };
+
+        let position = request.params.range.start;
+        let entry = tree
+            .entries()
+            .into_iter()
+            .find(|entry| !entry.is_comment() && entry.range().contains(position))?;
+
+        let content = entry.field("title")?.content.as_ref()?;
+        let text = &request.document().text;
+        let new_text = protect_title(text, content);
+        if new_text == CharStream::extract(text, content.range()) {
+            return None;
+        }
+
+        let mut changes = HashMap::new();
+        changes.insert(
+            request.document().uri.clone().into(),
+            vec![TextEdit::new(content.range(), new_text)],
+        );
+
+        Some(CodeAction {
+            title: "Protect capitalized words in title".into(),
+            kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+            diagnostics: None,
+            edit: Some(WorkspaceEdit::new(changes)),
+            command: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verify(text: &'static str, expected: Option<(Range, &str)>) {
+        let actions = test_feature(
+            BibtexTitleCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", text)],
+                main_file: "foo.bib",
+                position: Position::new(0, 1),
+                ..FeatureSpec::default()
+            },
+        );
+
+        let expected = expected.map(|(range, new_text)| {
+            let mut changes = HashMap::new();
+            changes.insert(
+                FeatureSpec::uri("foo.bib"),
+                vec![TextEdit::new(range, new_text.into())],
+            );
+            CodeAction {
+                title: "Protect capitalized words in title".into(),
+                kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+                diagnostics: None,
+                edit: Some(WorkspaceEdit::new(changes)),
+                command: None,
+            }
+        });
+        assert_eq!(actions, expected.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn acronym() {
+        verify(
+            "@article{foo, title = {Sequencing the DNA}}",
+            Some((Range::new_simple(0, 22, 0, 42), "{Sequencing the {DNA}}")),
+        );
+    }
+
+    #[test]
+    fn already_protected() {
+        verify("@article{foo, title = {Sequencing the {DNA}}}", None);
+    }
+
+    #[test]
+    fn no_title() {
+        verify("@article{foo, author = {Foo Bar}}", None);
+    }
+}
@@ -0,0 +1,187 @@
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+const VERBATIM_FIELDS: &[&str] = &[
+    "url", "doi", "eprint", "file", "isbn", "issn", "crossref", "xref", "xdata",
+];
+
+type Conversion = fn(&str, &BibtexContent) -> String;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BibtexUnicodeCodeActionProvider;
+
+impl FeatureProvider for BibtexUnicodeCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        let tree = match &request.document().tree {
+            SyntaxTree::Bibtex(tree) => tree,
+            SyntaxTree::Latex(_) => return Vec::new(),
+        };
+
+        let entries: Vec<&BibtexEntry> = tree
+            .entries()
+            .into_iter()
+            .filter(|entry| !entry.is_comment())
+            .collect();
+
+        let position = request.params.range.start;
+        let current_entries: Vec<&BibtexEntry> = entries
+            .iter()
+            .copied()
+            .filter(|entry| entry.range().contains(position))
+            .collect();
+
+        let conversions: &[(&str, Conversion)] = &[
+            ("Convert accents to Unicode", content_to_unicode),
+            ("Convert accents to LaTeX", content_to_latex),
+        ];
+
+        let mut actions = Vec::new();
+        for (title, convert) in conversions {
+            if !current_entries.is_empty() {
+                let title = format!("{} in entry", title);
+                actions.extend(Self::make_action(
+                    request,
+                    title,
+                    &current_entries,
+                    *convert,
+                ));
+            }
+
+            let title = format!("{} in file", title);
+            actions.extend(Self::make_action(request, title, &entries, *convert));
+        }
+        actions
+    }
+}
+
+impl BibtexUnicodeCodeActionProvider {
+    fn make_action(
+        request: &FeatureRequest<CodeActionParams>,
+        title: String,
+        entries: &[&BibtexEntry],
+        convert: Conversion,
+    ) -> Option<CodeAction> {
+        let text = &request.document().text;
+        let mut edits = Vec::new();
+        for field in entries.iter().flat_map(|entry| entry.fields.iter()) {
+            if VERBATIM_FIELDS.contains(&field.name.text().to_lowercase().as_str()) {
+                continue;
+            }
+
+            if let Some(content) = &field.content {
+                let new_text = convert(text, content);
+                if new_text != CharStream::extract(text, content.range()) {
+                    edits.push(TextEdit::new(content.range(), new_text));
+                }
+            }
+        }
+
+        if edits.is_empty() {
+            return None;
+        }
+
+        let mut changes = HashMap::new();
+        changes.insert(request.document().uri.clone().into(), edits);
+        Some(CodeAction {
+            title,
+            kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+            diagnostics: None,
+            edit: Some(WorkspaceEdit::new(changes)),
+            command: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verify(text: &'static str, position: Position, expected: Vec<(&str, Vec<TextEdit>)>) {
+        let actions = test_feature(
+            BibtexUnicodeCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", text)],
+                main_file: "foo.bib",
+                position,
+                ..FeatureSpec::default()
+            },
+        );
+
+        let expected: Vec<CodeAction> = expected
+            .into_iter()
+            .map(|(title, edits)| {
+                let mut changes = HashMap::new();
+                changes.insert(FeatureSpec::uri("foo.bib"), edits);
+                CodeAction {
+                    title: title.into(),
+                    kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+                    diagnostics: None,
+                    edit: Some(WorkspaceEdit::new(changes)),
+                    command: None,
+                }
+            })
+            .collect();
+        assert_eq!(actions, expected);
+    }
+
+    #[test]
+    fn to_unicode() {
+        let author = TextEdit::new(Range::new_simple(0, 23, 0, 35), "{Müller}".into());
+        let title = TextEdit::new(Range::new_simple(1, 22, 1, 30), "{Café}".into());
+        verify(
+            "@article{foo, author = {M{\\\"u}ller}}\n@article{bar, title = {Caf\\'e}}",
+            Position::new(0, 1),
+            vec![
+                ("Convert accents to Unicode in entry", vec![author.clone()]),
+                ("Convert accents to Unicode in file", vec![author, title]),
+            ],
+        );
+    }
+
+    #[test]
+    fn to_latex() {
+        let title = TextEdit::new(Range::new_simple(0, 22, 0, 28), "{Caf{\\'e}}".into());
+        verify(
+            "@article{foo, title = {Café}, url = {https://example.com/café}}",
+            Position::new(0, 1),
+            vec![
+                ("Convert accents to LaTeX in entry", vec![title.clone()]),
+                ("Convert accents to LaTeX in file", vec![title]),
+            ],
+        );
+    }
+
+    #[test]
+    fn outside_entry() {
+        let author = TextEdit::new(Range::new_simple(0, 23, 0, 35), "{Müller}".into());
+        verify(
+            "@article{foo, author = {M{\\\"u}ller}}\n",
+            Position::new(1, 0),
+            vec![("Convert accents to Unicode in file", vec![author])],
+        );
+    }
+
+    #[test]
+    fn latex() {
+        let actions = test_feature(
+            BibtexUnicodeCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\\"o")],
+                main_file: "foo.tex",
+                position: Position::new(0, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(actions.is_empty());
+    }
+}
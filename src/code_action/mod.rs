@@ -0,0 +1,48 @@
+mod bibtex_key;
+mod bibtex_name;
+mod bibtex_title;
+mod bibtex_unicode;
+
+use self::bibtex_key::BibtexKeyCodeActionProvider;
+use self::bibtex_name::BibtexNameCodeActionProvider;
+use self::bibtex_title::BibtexTitleCodeActionProvider;
+use self::bibtex_unicode::BibtexUnicodeCodeActionProvider;
+use futures_boxed::boxed;
+use texlab_protocol::{CodeAction, CodeActionParams};
+use texlab_workspace::*;
+
+pub struct CodeActionProvider {
+    provider: ConcatProvider<CodeActionParams, CodeAction>,
+}
+
+impl CodeActionProvider {
+    pub fn new() -> Self {
+        Self {
+            provider: ConcatProvider::new(vec![
+                Box::new(BibtexKeyCodeActionProvider),
+                Box::new(BibtexNameCodeActionProvider),
+                Box::new(BibtexTitleCodeActionProvider),
+                Box::new(BibtexUnicodeCodeActionProvider),
+            ]),
+        }
+    }
+}
+
+impl Default for CodeActionProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureProvider for CodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        self.provider.execute(request).await
+    }
+}
@@ -0,0 +1,153 @@
+use futures_boxed::boxed;
+use texlab_protocol::RangeExt;
+use texlab_protocol::{LocationLink, TextDocumentPositionParams};
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BibtexCrossrefDefinitionProvider;
+
+impl FeatureProvider for BibtexCrossrefDefinitionProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<LocationLink>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut links = Vec::new();
+        if let Some(reference) = Self::find_reference(&request) {
+            for document in request.related_documents() {
+                Self::find_definitions(&document, &reference, &mut links);
+            }
+        }
+        links
+    }
+}
+
+impl BibtexCrossrefDefinitionProvider {
+    fn find_definitions(
+        document: &Document,
+        reference: &BibtexToken,
+        links: &mut Vec<LocationLink>,
+    ) {
+        if let SyntaxTree::Bibtex(tree) = &document.tree {
+            for entry in tree.entries() {
+                if let Some(key) = &entry.key {
+                    if key.text() == reference.text() && !entry.is_comment() {
+                        let link = LocationLink {
+                            origin_selection_range: Some(reference.range()),
+                            target_uri: document.uri.clone().into(),
+                            target_range: entry.range(),
+                            target_selection_range: key.range(),
+                        };
+                        links.push(link);
+                    }
+                }
+            }
+        }
+    }
+
+    fn find_reference(
+        request: &FeatureRequest<TextDocumentPositionParams>,
+    ) -> Option<&BibtexToken> {
+        if let SyntaxTree::Bibtex(tree) = &request.document().tree {
+            tree.entries()
+                .into_iter()
+                .flat_map(|entry| entry.fields.iter())
+                .flat_map(BibtexField::crossref_keys)
+                .find(|key| key.range().contains(request.params.position))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Position, Range};
+
+    #[test]
+    fn crossref() {
+        let links = test_feature(
+            BibtexCrossrefDefinitionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.tex",
+                        "\\addbibresource{foo.bib}\n\\addbibresource{bar.bib}",
+                    ),
+                    FeatureSpec::file("foo.bib", "@inproceedings{foo, crossref = {bar}}"),
+                    FeatureSpec::file("bar.bib", "@proceedings{bar, year = {2020}}"),
+                ],
+                main_file: "foo.bib",
+                position: Position::new(0, 33),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            links,
+            vec![LocationLink {
+                origin_selection_range: Some(Range::new_simple(0, 32, 0, 35)),
+                target_uri: FeatureSpec::uri("bar.bib"),
+                target_range: Range::new_simple(0, 0, 0, 32),
+                target_selection_range: Range::new_simple(0, 13, 0, 16)
+            }]
+        );
+    }
+
+    #[test]
+    fn xdata_list() {
+        let links = test_feature(
+            BibtexCrossrefDefinitionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@book{foo, xdata = {bar, baz}}\n@xdata{baz,}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 26),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            links,
+            vec![LocationLink {
+                origin_selection_range: Some(Range::new_simple(0, 25, 0, 28)),
+                target_uri: FeatureSpec::uri("foo.bib"),
+                target_range: Range::new_simple(1, 0, 1, 12),
+                target_selection_range: Range::new_simple(1, 7, 1, 10)
+            }]
+        );
+    }
+
+    #[test]
+    fn other_field() {
+        let links = test_feature(
+            BibtexCrossrefDefinitionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@book{foo, title = {bar}}\n@book{bar,}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 21),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(links.is_empty());
+    }
+
+    #[test]
+    fn latex() {
+        let links = test_feature(
+            BibtexCrossrefDefinitionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "")],
+                main_file: "foo.tex",
+                position: Position::new(0, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(links.is_empty());
+    }
+}
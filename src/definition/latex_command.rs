@@ -27,6 +27,17 @@ impl FeatureProvider for LatexCommandDefinitionProvider {
                             })
                             .for_each(|def| definitions.push(def));
 
+                        tree.primitive_definitions
+                            .iter()
+                            .filter(|def| def.definition.name.text() == command.name.text())
+                            .map(|def| LocationLink {
+                                origin_selection_range: Some(command.range()),
+                                target_uri: document.uri.clone().into(),
+                                target_range: def.range(),
+                                target_selection_range: def.range(),
+                            })
+                            .for_each(|def| definitions.push(def));
+
                         tree.math
                             .operators
                             .iter()
@@ -78,6 +89,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn document_command_definition() {
+        let links = test_feature(
+            LatexCommandDefinitionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.tex", "\\include{bar}\n\\foo"),
+                    FeatureSpec::file("bar.tex", "\\NewDocumentCommand{\\foo}{m}{#1}"),
+                ],
+                main_file: "foo.tex",
+                position: Position::new(1, 3),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            links,
+            vec![LocationLink {
+                origin_selection_range: Some(Range::new_simple(1, 0, 1, 4)),
+                target_uri: FeatureSpec::uri("bar.tex"),
+                target_range: Range::new_simple(0, 0, 0, 32),
+                target_selection_range: Range::new_simple(0, 0, 0, 32),
+            }]
+        );
+    }
+
+    #[test]
+    fn primitive_definition() {
+        let links = test_feature(
+            LatexCommandDefinitionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\def\\foo#1{#1}\n\\let\\bar\\foo\n\\bar",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 2),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            links,
+            vec![LocationLink {
+                origin_selection_range: Some(Range::new_simple(2, 0, 2, 4)),
+                target_uri: FeatureSpec::uri("foo.tex"),
+                target_range: Range::new_simple(1, 0, 1, 12),
+                target_selection_range: Range::new_simple(1, 0, 1, 12),
+            }]
+        );
+    }
+
     #[test]
     fn math_operator() {
         let links = test_feature(
@@ -0,0 +1,96 @@
+use futures_boxed::boxed;
+use texlab_protocol::{LocationLink, RangeExt, TextDocumentPositionParams};
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexEnvironmentDefinitionProvider;
+
+impl FeatureProvider for LatexEnvironmentDefinitionProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<LocationLink>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut links = Vec::new();
+        if let Some(reference) = Self::find_reference(request) {
+            for document in request.related_documents() {
+                if let SyntaxTree::Latex(tree) = &document.tree {
+                    tree.environment_definitions
+                        .iter()
+                        .filter(|def| def.name().text() == reference.text())
+                        .map(|def| LocationLink {
+                            origin_selection_range: Some(reference.range()),
+                            target_uri: document.uri.clone().into(),
+                            target_range: def.range(),
+                            target_selection_range: def.name().range(),
+                        })
+                        .for_each(|link| links.push(link));
+                }
+            }
+        }
+        links
+    }
+}
+
+impl LatexEnvironmentDefinitionProvider {
+    fn find_reference(request: &FeatureRequest<TextDocumentPositionParams>) -> Option<&LatexToken> {
+        if let SyntaxTree::Latex(tree) = &request.document().tree {
+            tree.env
+                .environments
+                .iter()
+                .flat_map(|env| env.left.name().into_iter().chain(env.right.name()))
+                .find(|name| name.range().contains(request.params.position))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Position, Range};
+
+    #[test]
+    fn environment() {
+        let links = test_feature(
+            LatexEnvironmentDefinitionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.tex",
+                        "\\NewDocumentEnvironment{foo}{}{}{}\n\\include{bar}",
+                    ),
+                    FeatureSpec::file("bar.tex", "\\begin{foo}\n\\end{foo}"),
+                ],
+                main_file: "bar.tex",
+                position: Position::new(1, 6),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            links,
+            vec![LocationLink {
+                origin_selection_range: Some(Range::new_simple(1, 5, 1, 8)),
+                target_uri: FeatureSpec::uri("foo.tex"),
+                target_range: Range::new_simple(0, 0, 0, 34),
+                target_selection_range: Range::new_simple(0, 24, 0, 27),
+            }]
+        );
+    }
+
+    #[test]
+    fn bibtex() {
+        let links = test_feature(
+            LatexEnvironmentDefinitionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "")],
+                main_file: "foo.bib",
+                position: Position::new(0, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(links.is_empty());
+    }
+}
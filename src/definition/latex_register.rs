@@ -0,0 +1,119 @@
+use futures_boxed::boxed;
+use texlab_protocol::{LocationLink, RangeExt, TextDocumentPositionParams};
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexRegisterDefinitionProvider;
+
+impl FeatureProvider for LatexRegisterDefinitionProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<LocationLink>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut links = Vec::new();
+        if let Some(reference) = Self::find_reference(request) {
+            for document in request.related_documents() {
+                if let SyntaxTree::Latex(tree) = &document.tree {
+                    tree.registers
+                        .definitions(reference.kind)
+                        .filter(|register| register.name().text() == reference.name().text())
+                        .map(|register| LocationLink {
+                            origin_selection_range: Some(reference.name().range()),
+                            target_uri: document.uri.clone().into(),
+                            target_range: register.range(),
+                            target_selection_range: register.name().range(),
+                        })
+                        .for_each(|link| links.push(link));
+                }
+            }
+        }
+        links
+    }
+}
+
+impl LatexRegisterDefinitionProvider {
+    fn find_reference(
+        request: &FeatureRequest<TextDocumentPositionParams>,
+    ) -> Option<&LatexRegister> {
+        if let SyntaxTree::Latex(tree) = &request.document().tree {
+            tree.registers
+                .registers
+                .iter()
+                .find(|register| register.name().range().contains(request.params.position))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Position, Range};
+
+    #[test]
+    fn counter() {
+        let links = test_feature(
+            LatexRegisterDefinitionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.tex", "\\newcounter{foo}\n\\include{bar}"),
+                    FeatureSpec::file("bar.tex", "\\stepcounter{foo}"),
+                ],
+                main_file: "bar.tex",
+                position: Position::new(0, 14),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            links,
+            vec![LocationLink {
+                origin_selection_range: Some(Range::new_simple(0, 13, 0, 16)),
+                target_uri: FeatureSpec::uri("foo.tex"),
+                target_range: Range::new_simple(0, 0, 0, 16),
+                target_selection_range: Range::new_simple(0, 12, 0, 15),
+            }]
+        );
+    }
+
+    #[test]
+    fn length() {
+        let links = test_feature(
+            LatexRegisterDefinitionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\newlength{\\foo}\n\\newsavebox{\\foo}\n\\setlength{\\foo}{1cm}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 13),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            links,
+            vec![LocationLink {
+                origin_selection_range: Some(Range::new_simple(2, 11, 2, 15)),
+                target_uri: FeatureSpec::uri("foo.tex"),
+                target_range: Range::new_simple(0, 0, 0, 16),
+                target_selection_range: Range::new_simple(0, 11, 0, 15),
+            }]
+        );
+    }
+
+    #[test]
+    fn bibtex() {
+        let links = test_feature(
+            LatexRegisterDefinitionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "")],
+                main_file: "foo.bib",
+                position: Position::new(0, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(links.is_empty());
+    }
+}
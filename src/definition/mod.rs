@@ -1,12 +1,18 @@
+mod bibtex_crossref;
 mod bibtex_string;
 mod latex_citation;
 mod latex_command;
+mod latex_environment;
 mod latex_label;
+mod latex_register;
 
+use self::bibtex_crossref::BibtexCrossrefDefinitionProvider;
 use self::bibtex_string::BibtexStringDefinitionProvider;
 use self::latex_citation::LatexCitationDefinitionProvider;
 use self::latex_command::LatexCommandDefinitionProvider;
+use self::latex_environment::LatexEnvironmentDefinitionProvider;
 use self::latex_label::LatexLabelDefinitionProvider;
+use self::latex_register::LatexRegisterDefinitionProvider;
 use futures_boxed::boxed;
 use texlab_protocol::{LocationLink, TextDocumentPositionParams};
 use texlab_workspace::*;
@@ -19,10 +25,13 @@ impl DefinitionProvider {
     pub fn new() -> Self {
         Self {
             provider: ConcatProvider::new(vec![
+                Box::new(BibtexCrossrefDefinitionProvider),
                 Box::new(BibtexStringDefinitionProvider),
                 Box::new(LatexCitationDefinitionProvider),
                 Box::new(LatexCommandDefinitionProvider),
+                Box::new(LatexEnvironmentDefinitionProvider),
                 Box::new(LatexLabelDefinitionProvider),
+                Box::new(LatexRegisterDefinitionProvider),
             ]),
         }
     }
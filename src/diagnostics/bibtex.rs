@@ -1,4 +1,6 @@
-use texlab_protocol::{Diagnostic, DiagnosticSeverity, Position, Range};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use texlab_protocol::*;
 use texlab_syntax::*;
 use texlab_workspace::Document;
 
@@ -167,16 +169,508 @@ impl Into<Diagnostic> for BibtexError {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BibtexNameErrorCode {
+    CommaSeparator,
+    EmptyName,
+    TrailingAnd,
+    UnbalancedBraces,
+}
+
+impl BibtexNameErrorCode {
+    pub fn message(self) -> &'static str {
+        match self {
+            BibtexNameErrorCode::CommaSeparator => {
+                "Names should be separated by \"and\" instead of \",\""
+            }
+            BibtexNameErrorCode::EmptyName => "Empty name in name list",
+            BibtexNameErrorCode::TrailingAnd => "Trailing \"and\" in name list",
+            BibtexNameErrorCode::UnbalancedBraces => "Unbalanced braces in name list",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BibtexNameError {
+    code: BibtexNameErrorCode,
+    range: Range,
+}
+
+impl BibtexNameError {
+    pub fn new(code: BibtexNameErrorCode, range: Range) -> Self {
+        Self { code, range }
+    }
+
+    pub fn analyze(tree: &BibtexSyntaxTree) -> Vec<Self> {
+        let mut errors = Vec::new();
+        for entry in tree.entries() {
+            if entry.is_comment() {
+                continue;
+            }
+
+            for field in &entry.fields {
+                let name = field.name.text().to_lowercase();
+                if !BIBTEX_NAME_FIELDS.contains(&name.as_str()) {
+                    continue;
+                }
+
+                let content = match &field.content {
+                    Some(content) => content,
+                    None => continue,
+                };
+
+                if Self::has_unbalanced_braces(content, true) {
+                    errors.push(Self::new(
+                        BibtexNameErrorCode::UnbalancedBraces,
+                        content.range(),
+                    ));
+                    continue;
+                }
+
+                let list = match BibtexNameList::parse(content) {
+                    Some(list) => list,
+                    None => continue,
+                };
+
+                for (i, separator) in list.separators.iter().enumerate() {
+                    if i == list.separators.len() - 1 && list.names[i + 1].parts.is_empty() {
+                        errors.push(Self::new(
+                            BibtexNameErrorCode::TrailingAnd,
+                            separator.range(),
+                        ));
+                    } else if list.names[i].parts.is_empty() {
+                        errors.push(Self::new(BibtexNameErrorCode::EmptyName, separator.range()));
+                    }
+                }
+
+                for name in &list.names {
+                    if name.is_comma_separated_list() {
+                        errors.push(Self::new(
+                            BibtexNameErrorCode::CommaSeparator,
+                            name.range().unwrap(),
+                        ));
+                    }
+                }
+            }
+        }
+        errors
+    }
+
+    fn has_unbalanced_braces(content: &BibtexContent, is_top_level: bool) -> bool {
+        match content {
+            BibtexContent::BracedContent(content) => {
+                content.right.is_none()
+                    || content.children.iter().any(|child| {
+                        (is_top_level && Self::is_assign(child))
+                            || Self::has_unbalanced_braces(child, false)
+                    })
+            }
+            BibtexContent::QuotedContent(content) => content
+                .children
+                .iter()
+                .any(|child| Self::has_unbalanced_braces(child, false)),
+            BibtexContent::Concat(concat) => {
+                Self::has_unbalanced_braces(&concat.left, is_top_level)
+                    || concat.right.as_ref().map_or(false, |right| {
+                        Self::has_unbalanced_braces(right, is_top_level)
+                    })
+            }
+            BibtexContent::Word(_) | BibtexContent::Command(_) => false,
+        }
+    }
+
+    fn is_assign(content: &BibtexContent) -> bool {
+        match content {
+            BibtexContent::Word(word) => word.token.kind == BibtexTokenKind::Assign,
+            _ => false,
+        }
+    }
+}
+
+impl Into<Diagnostic> for BibtexNameError {
+    fn into(self) -> Diagnostic {
+        Diagnostic {
+            source: Some("bibtex".into()),
+            range: self.range,
+            message: self.code.message().into(),
+            severity: Some(DiagnosticSeverity::Warning),
+            code: None,
+            related_information: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BibtexDuplicateKind {
+    Key,
+    Doi,
+    TitleAndYear,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BibtexDuplicate {
+    kind: BibtexDuplicateKind,
+    key: String,
+    range: Range,
+    other_key: String,
+    other_location: Location,
+}
+
+impl BibtexDuplicate {
+    pub fn analyze(document: &Document, related_documents: &[Arc<Document>]) -> Vec<Self> {
+        let tree = match &document.tree {
+            SyntaxTree::Bibtex(tree) => tree,
+            SyntaxTree::Latex(_) => return Vec::new(),
+        };
+
+        let mut others = Vec::new();
+        let mut indices_by_key: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut indices_by_doi: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut indices_by_title_and_year: HashMap<(String, String), Vec<usize>> = HashMap::new();
+        for other_document in related_documents {
+            if let SyntaxTree::Bibtex(other_tree) = &other_document.tree {
+                for other_entry in other_tree.entries() {
+                    if let Some(other_key) = Self::key(other_entry) {
+                        let index = others.len();
+                        indices_by_key
+                            .entry(other_key.text())
+                            .or_default()
+                            .push(index);
+                        if let Some(doi) = Self::doi(other_entry) {
+                            indices_by_doi.entry(doi).or_default().push(index);
+                        }
+                        if let Some(title_and_year) = Self::title_and_year(other_entry) {
+                            indices_by_title_and_year
+                                .entry(title_and_year)
+                                .or_default()
+                                .push(index);
+                        }
+                        others.push((&other_document.uri, other_entry, other_key));
+                    }
+                }
+            }
+        }
+
+        let mut duplicates = Vec::new();
+        for entry in tree.entries() {
+            let key = match Self::key(entry) {
+                Some(key) => key,
+                None => continue,
+            };
+
+            let mut matches = Vec::new();
+            let candidates = vec![
+                (indices_by_key.get(key.text()), BibtexDuplicateKind::Key),
+                (
+                    Self::doi(entry).and_then(|doi| indices_by_doi.get(&doi)),
+                    BibtexDuplicateKind::Doi,
+                ),
+                (
+                    Self::title_and_year(entry)
+                        .and_then(|title_and_year| indices_by_title_and_year.get(&title_and_year)),
+                    BibtexDuplicateKind::TitleAndYear,
+                ),
+            ];
+            for (indices, kind) in candidates {
+                for index in indices.into_iter().flatten() {
+                    matches.push((*index, kind));
+                }
+            }
+            matches.sort_by_key(|(index, _)| *index);
+            matches.dedup_by_key(|(index, _)| *index);
+
+            for (index, kind) in matches {
+                let (other_uri, other_entry, other_key) = &others[index];
+                if *other_uri == &document.uri && other_entry.range == entry.range {
+                    continue;
+                }
+
+                duplicates.push(Self {
+                    kind,
+                    key: key.text().to_owned(),
+                    range: key.range(),
+                    other_key: other_key.text().to_owned(),
+                    other_location: Location::new((*other_uri).clone().into(), other_key.range()),
+                });
+            }
+        }
+        duplicates
+    }
+
+    fn key(entry: &BibtexEntry) -> Option<&BibtexToken> {
+        if entry.is_comment() {
+            None
+        } else {
+            entry.key.as_ref()
+        }
+    }
+
+    fn field_text(entry: &BibtexEntry, name: &str) -> Option<String> {
+        let text = entry.field(name)?.content.as_ref()?.text();
+        if text.trim().is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
+
+    fn doi(entry: &BibtexEntry) -> Option<String> {
+        let mut doi = Self::field_text(entry, "doi")?.trim().to_lowercase();
+        for prefix in &[
+            "https://doi.org/",
+            "http://doi.org/",
+            "https://dx.doi.org/",
+            "http://dx.doi.org/",
+            "doi:",
+        ] {
+            if doi.starts_with(prefix) {
+                doi = doi[prefix.len()..].trim().to_owned();
+            }
+        }
+        Some(doi)
+    }
+
+    fn title_and_year(entry: &BibtexEntry) -> Option<(String, String)> {
+        let title = Self::field_text(entry, "title")?
+            .chars()
+            .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+            .collect::<String>()
+            .to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let year = Self::field_text(entry, "year")
+            .or_else(|| Self::field_text(entry, "date"))?
+            .chars()
+            .take(4)
+            .collect();
+
+        Some((title, year))
+    }
+}
+
+impl Into<Diagnostic> for BibtexDuplicate {
+    fn into(self) -> Diagnostic {
+        let (severity, message, related_message) = match self.kind {
+            BibtexDuplicateKind::Key => (
+                DiagnosticSeverity::Error,
+                format!("Duplicate entry key: \"{}\"", self.key),
+                "Also defined here".to_owned(),
+            ),
+            BibtexDuplicateKind::Doi => (
+                DiagnosticSeverity::Warning,
+                format!("Probable duplicate of \"{}\" (same DOI)", self.other_key),
+                format!("\"{}\" is defined here", self.other_key),
+            ),
+            BibtexDuplicateKind::TitleAndYear => (
+                DiagnosticSeverity::Warning,
+                format!(
+                    "Probable duplicate of \"{}\" (same title and year)",
+                    self.other_key
+                ),
+                format!("\"{}\" is defined here", self.other_key),
+            ),
+        };
+
+        Diagnostic {
+            source: Some("bibtex".into()),
+            range: self.range,
+            message,
+            severity: Some(severity),
+            code: None,
+            related_information: Some(vec![DiagnosticRelatedInformation {
+                location: self.other_location,
+                message: related_message,
+            }]),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BibtexCrossrefError {
+    field: String,
+    key: String,
+    range: Range,
+}
+
+impl BibtexCrossrefError {
+    pub fn analyze(document: &Document, related_documents: &[Arc<Document>]) -> Vec<Self> {
+        let tree = match &document.tree {
+            SyntaxTree::Bibtex(tree) => tree,
+            SyntaxTree::Latex(_) => return Vec::new(),
+        };
+
+        let trees: Vec<&BibtexSyntaxTree> = related_documents
+            .iter()
+            .filter_map(|document| match &document.tree {
+                SyntaxTree::Bibtex(tree) => Some(tree),
+                SyntaxTree::Latex(_) => None,
+            })
+            .collect();
+
+        let mut errors = Vec::new();
+        for entry in tree.entries() {
+            if entry.is_comment() {
+                continue;
+            }
+
+            for field in &entry.fields {
+                for key in field.crossref_keys() {
+                    if find_entry(&trees, key.text()).is_none() {
+                        errors.push(Self {
+                            field: field.name.text().to_lowercase(),
+                            key: key.text().to_owned(),
+                            range: key.range(),
+                        });
+                    }
+                }
+            }
+        }
+        errors
+    }
+}
+
+impl Into<Diagnostic> for BibtexCrossrefError {
+    fn into(self) -> Diagnostic {
+        Diagnostic {
+            source: Some("bibtex".into()),
+            range: self.range,
+            message: format!("Unknown {} target: \"{}\"", self.field, self.key),
+            severity: Some(DiagnosticSeverity::Warning),
+            code: None,
+            related_information: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BibtexMissingFieldError {
+    entry_type: String,
+    fields: Vec<String>,
+    range: Range,
+}
+
+impl BibtexMissingFieldError {
+    pub fn analyze(document: &Document, related_documents: &[Arc<Document>]) -> Vec<Self> {
+        let tree = match &document.tree {
+            SyntaxTree::Bibtex(tree) => tree,
+            SyntaxTree::Latex(_) => return Vec::new(),
+        };
+
+        let trees: Vec<&BibtexSyntaxTree> = related_documents
+            .iter()
+            .filter_map(|document| match &document.tree {
+                SyntaxTree::Bibtex(tree) => Some(tree),
+                SyntaxTree::Latex(_) => None,
+            })
+            .collect();
+
+        let mut errors = Vec::new();
+        for entry in tree.entries() {
+            let key = match &entry.key {
+                Some(key) if !entry.is_comment() => key,
+                _ => continue,
+            };
+
+            // An entry without any fields is still being written.
+            if entry.fields.is_empty() {
+                continue;
+            }
+
+            let entry_type = match LANGUAGE_DATA.find_entry_type(&entry.ty.text()[1..]) {
+                Some(entry_type) => entry_type,
+                None => continue,
+            };
+
+            let mut names: HashSet<String> = entry
+                .fields
+                .iter()
+                .map(|field| field.name.text().to_lowercase())
+                .collect();
+            for inherited in inherited_fields(&trees, entry) {
+                names.insert(inherited.field.name.text().to_lowercase());
+            }
+
+            let fields: Vec<String> = entry_type
+                .required_fields
+                .iter()
+                .filter(|field| field.split('|').all(|name| !names.contains(name)))
+                .cloned()
+                .collect();
+
+            if !fields.is_empty() {
+                errors.push(Self {
+                    entry_type: entry_type.name.clone(),
+                    fields,
+                    range: key.range(),
+                });
+            }
+        }
+        errors
+    }
+}
+
+impl Into<Diagnostic> for BibtexMissingFieldError {
+    fn into(self) -> Diagnostic {
+        let fields: Vec<_> = self
+            .fields
+            .iter()
+            .map(|field| {
+                field
+                    .split('|')
+                    .map(|name| format!("\"{}\"", name))
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            })
+            .collect();
+
+        Diagnostic {
+            source: Some("bibtex".into()),
+            range: self.range,
+            message: format!(
+                "Missing required fields for @{}: {}",
+                self.entry_type,
+                fields.join(", ")
+            ),
+            severity: Some(DiagnosticSeverity::Warning),
+            code: None,
+            related_information: None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub struct BibtexDiagnosticsProvider;
 
 impl BibtexDiagnosticsProvider {
-    pub fn get(self, document: &Document) -> Vec<Diagnostic> {
+    pub fn get(self, document: &Document, related_documents: &[Arc<Document>]) -> Vec<Diagnostic> {
         if let SyntaxTree::Bibtex(tree) = &document.tree {
-            BibtexError::analyze(&tree)
+            let mut diagnostics: Vec<Diagnostic> = BibtexError::analyze(&tree)
+                .into_iter()
+                .map(Into::into)
+                .collect();
+
+            BibtexNameError::analyze(&tree)
+                .into_iter()
+                .map(Into::into)
+                .for_each(|diagnostic| diagnostics.push(diagnostic));
+
+            BibtexDuplicate::analyze(document, related_documents)
+                .into_iter()
+                .map(Into::into)
+                .for_each(|diagnostic| diagnostics.push(diagnostic));
+
+            BibtexCrossrefError::analyze(document, related_documents)
+                .into_iter()
+                .map(Into::into)
+                .for_each(|diagnostic| diagnostics.push(diagnostic));
+
+            BibtexMissingFieldError::analyze(document, related_documents)
                 .into_iter()
                 .map(Into::into)
-                .collect()
+                .for_each(|diagnostic| diagnostics.push(diagnostic));
+            diagnostics
         } else {
             Vec::new()
         }
@@ -186,6 +680,7 @@ impl BibtexDiagnosticsProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use texlab_workspace::TestWorkspaceBuilder;
 
     #[test]
     fn begin_brace() {
@@ -317,4 +812,318 @@ mod tests {
         let errors = BibtexError::analyze(&text.into());
         assert_eq!(errors, Vec::new());
     }
+
+    fn analyze_duplicates(files: Vec<(&str, &str)>, main_file: &str) -> Vec<BibtexDuplicate> {
+        let mut builder = TestWorkspaceBuilder::new();
+        for (name, text) in files {
+            builder.add_document(name, text);
+        }
+        let uri = Uri::from_file_path(std::env::temp_dir().join(main_file)).unwrap();
+        let document = builder.workspace.find(&uri).unwrap();
+        let related_documents = builder
+            .workspace
+            .related_documents(&uri, &Options::default());
+        BibtexDuplicate::analyze(&document, &related_documents)
+    }
+
+    #[test]
+    fn duplicate_key_same_file() {
+        let duplicates =
+            analyze_duplicates(vec![("foo.bib", "@article{foo,}\n@book{foo,}")], "foo.bib");
+        let kinds: Vec<_> = duplicates.iter().map(|dup| dup.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![BibtexDuplicateKind::Key, BibtexDuplicateKind::Key]
+        );
+        assert_eq!(duplicates[0].range, Range::new_simple(0, 9, 0, 12));
+        assert_eq!(
+            duplicates[0].other_location.range,
+            Range::new_simple(1, 6, 1, 9)
+        );
+    }
+
+    #[test]
+    fn duplicate_key_related_file() {
+        let duplicates = analyze_duplicates(
+            vec![
+                ("foo.tex", "\\bibliography{foo,bar}"),
+                ("foo.bib", "@article{foo,}"),
+                ("bar.bib", "@article{foo,}"),
+            ],
+            "foo.bib",
+        );
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].kind, BibtexDuplicateKind::Key);
+        assert_eq!(
+            duplicates[0].other_location.uri.as_str(),
+            Uri::from_file_path(std::env::temp_dir().join("bar.bib"))
+                .unwrap()
+                .as_str()
+        );
+    }
+
+    #[test]
+    fn duplicate_key_unrelated_file() {
+        let duplicates = analyze_duplicates(
+            vec![("foo.bib", "@article{foo,}"), ("bar.bib", "@article{foo,}")],
+            "foo.bib",
+        );
+        assert_eq!(duplicates, Vec::new());
+    }
+
+    #[test]
+    fn duplicate_doi() {
+        let duplicates = analyze_duplicates(
+            vec![(
+                "foo.bib",
+                "@article{foo, doi = {10.1000/XYZ}}\n@article{bar, doi = {https://doi.org/10.1000/xyz}}",
+            )],
+            "foo.bib",
+        );
+        let kinds: Vec<_> = duplicates.iter().map(|dup| dup.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![BibtexDuplicateKind::Doi, BibtexDuplicateKind::Doi]
+        );
+        assert_eq!(duplicates[0].other_key, "bar");
+    }
+
+    #[test]
+    fn duplicate_title_and_year() {
+        let duplicates = analyze_duplicates(
+            vec![(
+                "foo.bib",
+                "@article{foo, title = {The {C}omputer}, year = 2020}\n\
+                 @inproceedings{bar, title = \"The computer.\", date = {2020-01-01}}",
+            )],
+            "foo.bib",
+        );
+        let kinds: Vec<_> = duplicates.iter().map(|dup| dup.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                BibtexDuplicateKind::TitleAndYear,
+                BibtexDuplicateKind::TitleAndYear
+            ]
+        );
+    }
+
+    #[test]
+    fn different_entries() {
+        let duplicates = analyze_duplicates(
+            vec![(
+                "foo.bib",
+                "@article{foo, title = {Foo}, year = 2020}\n@article{bar, title = {Foo}, year = 2019}",
+            )],
+            "foo.bib",
+        );
+        assert_eq!(duplicates, Vec::new());
+    }
+
+    fn analyze_crossrefs(files: Vec<(&str, &str)>, main_file: &str) -> Vec<BibtexCrossrefError> {
+        let mut builder = TestWorkspaceBuilder::new();
+        for (name, text) in files {
+            builder.add_document(name, text);
+        }
+        let uri = Uri::from_file_path(std::env::temp_dir().join(main_file)).unwrap();
+        let document = builder.workspace.find(&uri).unwrap();
+        let related_documents = builder
+            .workspace
+            .related_documents(&uri, &Options::default());
+        BibtexCrossrefError::analyze(&document, &related_documents)
+    }
+
+    #[test]
+    fn crossref_missing() {
+        let errors = analyze_crossrefs(
+            vec![("foo.bib", "@inproceedings{foo, crossref = {bar}}")],
+            "foo.bib",
+        );
+        assert_eq!(
+            errors,
+            vec![BibtexCrossrefError {
+                field: "crossref".into(),
+                key: "bar".into(),
+                range: Range::new_simple(0, 32, 0, 35),
+            }]
+        );
+    }
+
+    #[test]
+    fn crossref_related_file() {
+        let errors = analyze_crossrefs(
+            vec![
+                ("foo.tex", "\\bibliography{foo,bar}"),
+                ("foo.bib", "@inproceedings{foo, crossref = {bar}}"),
+                ("bar.bib", "@proceedings{bar,}"),
+            ],
+            "foo.bib",
+        );
+        assert_eq!(errors, Vec::new());
+    }
+
+    #[test]
+    fn crossref_xdata_list() {
+        let errors = analyze_crossrefs(
+            vec![("foo.bib", "@book{foo, xdata = {bar, baz}}\n@xdata{baz,}")],
+            "foo.bib",
+        );
+        assert_eq!(
+            errors,
+            vec![BibtexCrossrefError {
+                field: "xdata".into(),
+                key: "bar".into(),
+                range: Range::new_simple(0, 20, 0, 23),
+            }]
+        );
+    }
+
+    fn analyze_missing_fields(
+        files: Vec<(&str, &str)>,
+        main_file: &str,
+    ) -> Vec<BibtexMissingFieldError> {
+        let mut builder = TestWorkspaceBuilder::new();
+        for (name, text) in files {
+            builder.add_document(name, text);
+        }
+        let uri = Uri::from_file_path(std::env::temp_dir().join(main_file)).unwrap();
+        let document = builder.workspace.find(&uri).unwrap();
+        let related_documents = builder
+            .workspace
+            .related_documents(&uri, &Options::default());
+        BibtexMissingFieldError::analyze(&document, &related_documents)
+    }
+
+    #[test]
+    fn missing_fields() {
+        let errors = analyze_missing_fields(
+            vec![("foo.bib", "@article{foo, author = {Foo}, title = {Bar}}")],
+            "foo.bib",
+        );
+        assert_eq!(
+            errors,
+            vec![BibtexMissingFieldError {
+                entry_type: "article".into(),
+                fields: vec!["journaltitle|journal".into(), "year|date".into()],
+                range: Range::new_simple(0, 9, 0, 12),
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_fields_alternatives() {
+        let errors = analyze_missing_fields(
+            vec![(
+                "foo.bib",
+                "@book{foo, editor = {Foo}, title = {Bar}, date = {2020}}
+                 @misc{bar, note = {Baz}}
+                 @manual{baz, title = {Qux}}",
+            )],
+            "foo.bib",
+        );
+        assert_eq!(errors, Vec::new());
+    }
+
+    #[test]
+    fn missing_fields_empty_entry() {
+        let errors = analyze_missing_fields(vec![("foo.bib", "@article{foo,}")], "foo.bib");
+        assert_eq!(errors, Vec::new());
+    }
+
+    #[test]
+    fn missing_fields_message() {
+        let error = BibtexMissingFieldError {
+            entry_type: "book".into(),
+            fields: vec!["author|editor".into()],
+            range: Range::new_simple(0, 6, 0, 9),
+        };
+        let diagnostic: Diagnostic = error.into();
+        assert_eq!(
+            diagnostic.message,
+            "Missing required fields for @book: \"author\" or \"editor\""
+        );
+    }
+
+    #[test]
+    fn missing_fields_inherited() {
+        let errors = analyze_missing_fields(
+            vec![(
+                "foo.bib",
+                "@inproceedings{foo, author = {Foo}, title = {Bar}, crossref = {bar}}\n\
+                 @proceedings{bar, title = {Baz}, booktitle = {Baz}, date = {2020}}",
+            )],
+            "foo.bib",
+        );
+        assert_eq!(errors, Vec::new());
+    }
+
+    #[test]
+    fn name_comma_separator() {
+        let errors = BibtexNameError::analyze(
+            &"@article{foo, author = {Foo Bar, Baz Qux, Quux Corge}}".into(),
+        );
+        assert_eq!(
+            errors,
+            vec![BibtexNameError::new(
+                BibtexNameErrorCode::CommaSeparator,
+                Range::new_simple(0, 24, 0, 52)
+            )]
+        );
+    }
+
+    #[test]
+    fn name_multi_word_family() {
+        let errors = BibtexNameError::analyze(
+            &"@article{foo, author = {García Márquez, Gabriel José and De La Cruz, Maria Elena}}"
+                .into(),
+        );
+        assert_eq!(errors, Vec::new());
+    }
+
+    #[test]
+    fn name_trailing_and() {
+        let errors = BibtexNameError::analyze(&"@article{foo, editor = {Foo Bar and}}".into());
+        assert_eq!(
+            errors,
+            vec![BibtexNameError::new(
+                BibtexNameErrorCode::TrailingAnd,
+                Range::new_simple(0, 32, 0, 35)
+            )]
+        );
+    }
+
+    #[test]
+    fn name_empty() {
+        let errors =
+            BibtexNameError::analyze(&"@article{foo, author = {Foo Bar and and Baz Qux}}".into());
+        assert_eq!(
+            errors,
+            vec![BibtexNameError::new(
+                BibtexNameErrorCode::EmptyName,
+                Range::new_simple(0, 36, 0, 39)
+            )]
+        );
+    }
+
+    #[test]
+    fn name_unbalanced_braces() {
+        let errors = BibtexNameError::analyze(
+            &"@article{foo, author = {{Google Inc. and Foo Bar},\ntitle = {Baz}}".into(),
+        );
+        assert_eq!(
+            errors,
+            vec![BibtexNameError::new(
+                BibtexNameErrorCode::UnbalancedBraces,
+                Range::new_simple(0, 23, 1, 14)
+            )]
+        );
+    }
+
+    #[test]
+    fn name_valid() {
+        let text = "@article{foo, author = {{Barnes and Noble} and van Gerwen, Michael and \
+                    Friedman, Jr., George and Ritchie, Dennis M.}}";
+        let errors = BibtexNameError::analyze(&text.into());
+        assert_eq!(errors, Vec::new());
+    }
 }
@@ -0,0 +1,75 @@
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct GraphicsDiagnosticsProvider;
+
+impl GraphicsDiagnosticsProvider {
+    pub fn get(
+        self,
+        workspace: &Workspace,
+        document: &Document,
+        options: &Options,
+    ) -> Vec<Diagnostic> {
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) if document.is_file() => tree,
+            _ => return Vec::new(),
+        };
+
+        let directories = graphics_directories(workspace, &document.uri, options);
+        let mut diagnostics = Vec::new();
+        for include in tree
+            .includes
+            .iter()
+            .filter(|include| include.kind.is_graphics())
+        {
+            let all_targets = graphics_targets(include, &directories);
+            for (path, targets) in include.paths().into_iter().zip(all_targets) {
+                let exists = targets.iter().any(|target| {
+                    target
+                        .to_file_path()
+                        .map(|path| path.is_file())
+                        .unwrap_or(false)
+                });
+
+                if !exists {
+                    diagnostics.push(Diagnostic {
+                        source: Some("latex".into()),
+                        range: path.range(),
+                        message: format!("Graphics file not found: \"{}\"", path.text()),
+                        severity: Some(DiagnosticSeverity::Warning),
+                        code: None,
+                        related_information: None,
+                    });
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn missing_graphics() {
+        let directory = tempdir().unwrap();
+        fs::write(directory.path().join("foo.pdf"), "").unwrap();
+
+        let mut builder = TestWorkspaceBuilder::new();
+        let text = format!(
+            "\\graphicspath{{{{{}/}}}}\n\\includegraphics{{foo}}\n\\includegraphics{{bar}}",
+            directory.path().to_string_lossy().replace('\\', "/")
+        );
+        let uri = builder.add_document("foo.tex", &text);
+        let document = builder.workspace.find(&uri).unwrap();
+        let diagnostics =
+            GraphicsDiagnosticsProvider.get(&builder.workspace, &document, &Options::default());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(2, 17, 2, 20));
+    }
+}
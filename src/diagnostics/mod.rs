@@ -1,28 +1,42 @@
 mod bibtex;
 mod build;
+mod graphics;
 mod latex;
+mod register;
 
 pub use self::bibtex::BibtexErrorCode;
 
 use self::bibtex::BibtexDiagnosticsProvider;
 use self::build::BuildDiagnosticsProvider;
+use self::graphics::GraphicsDiagnosticsProvider;
 use self::latex::LatexDiagnosticsProvider;
-use texlab_protocol::Diagnostic;
-use texlab_workspace::Document;
+use self::register::RegisterDiagnosticsProvider;
+use texlab_protocol::{Diagnostic, Options};
+use texlab_workspace::{Document, Workspace};
 
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct DiagnosticsManager {
     pub build: BuildDiagnosticsProvider,
     pub latex: LatexDiagnosticsProvider,
     pub bibtex: BibtexDiagnosticsProvider,
+    pub graphics: GraphicsDiagnosticsProvider,
+    pub register: RegisterDiagnosticsProvider,
 }
 
 impl DiagnosticsManager {
-    pub fn get(&self, document: &Document) -> Vec<Diagnostic> {
+    pub fn get(
+        &self,
+        workspace: &Workspace,
+        document: &Document,
+        options: &Options,
+    ) -> Vec<Diagnostic> {
+        let related_documents = workspace.related_documents(&document.uri, options);
         let mut diagnostics = Vec::new();
         diagnostics.append(&mut self.build.get(document));
         diagnostics.append(&mut self.latex.get(document));
-        diagnostics.append(&mut self.bibtex.get(document));
+        diagnostics.append(&mut self.bibtex.get(document, &related_documents));
+        diagnostics.append(&mut self.graphics.get(workspace, document, options));
+        diagnostics.append(&mut self.register.get(document, &related_documents));
         diagnostics
     }
 }
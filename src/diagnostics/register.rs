@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct RegisterDiagnosticsProvider;
+
+impl RegisterDiagnosticsProvider {
+    pub fn get(self, document: &Document, related_documents: &[Arc<Document>]) -> Vec<Diagnostic> {
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return Vec::new(),
+        };
+
+        let mut diagnostics = Vec::new();
+        for register in tree
+            .registers
+            .registers
+            .iter()
+            .filter(|reg| !reg.definition)
+        {
+            let name = register.name();
+            let is_defined = |reg: &LatexRegister| {
+                reg.definition && reg.kind == register.kind && reg.name().text() == name.text()
+            };
+
+            let defined_later = match tree.registers.registers.iter().find(|reg| is_defined(reg)) {
+                Some(definition) => definition.start() > register.start(),
+                None => false,
+            };
+
+            let defined_elsewhere = related_documents
+                .iter()
+                .filter(|doc| doc.uri != document.uri)
+                .any(|doc| match &doc.tree {
+                    SyntaxTree::Latex(tree) => tree.registers.registers.iter().any(is_defined),
+                    SyntaxTree::Bibtex(_) => false,
+                });
+
+            if defined_later && !defined_elsewhere {
+                diagnostics.push(Diagnostic {
+                    source: Some("latex".into()),
+                    range: name.range(),
+                    message: format!("\"{}\" is used before its definition", name.text()),
+                    severity: Some(DiagnosticSeverity::Warning),
+                    code: None,
+                    related_information: None,
+                });
+            }
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn use_before_definition() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document(
+            "foo.tex",
+            "\\stepcounter{foo}\n\\newcounter{foo}\n\\stepcounter{foo}\n\\setlength{\\bar}{1cm}",
+        );
+        let document = builder.workspace.find(&uri).unwrap();
+        let diagnostics = RegisterDiagnosticsProvider.get(&document, &[Arc::clone(&document)]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 13, 0, 16));
+    }
+
+    #[test]
+    fn defined_in_related_document() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", "\\stepcounter{foo}\n\\newcounter{foo}");
+        builder.add_document("bar.tex", "\\newcounter{foo}");
+        let documents = builder.workspace.documents().to_vec();
+        let document = builder.workspace.find(&uri).unwrap();
+        let diagnostics = RegisterDiagnosticsProvider.get(&document, &documents);
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,285 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use texlab_protocol::*;
+use texlab_workspace::Project;
+
+pub static SCALAR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("(?m)^\\s*\\$(?P<name>pdf_mode|out_dir|aux_dir)\\s*=\\s*(\"(?P<double>[^\"]*)\"|'(?P<single>[^']*)'|(?P<bare>[^;\\s#]+))\\s*;").unwrap()
+});
+
+pub static DEFAULT_FILES_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("(?m)^\\s*@default_files\\s*=\\s*\\((?P<files>[^)]*)\\)").unwrap());
+
+pub static STRING_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("\"(?P<double>[^\"]*)\"|'(?P<single>[^']*)'").unwrap());
+
+static FILE_CACHE: Lazy<Mutex<HashMap<PathBuf, (SystemTime, Latexmkrc)>>> =
+    Lazy::new(Mutex::default);
+
+const PROJECT_FILE_NAMES: &[&str] = &["latexmkrc", ".latexmkrc"];
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Latexmkrc {
+    pub pdf_mode: Option<u8>,
+    pub out_dir: Option<PathBuf>,
+    pub aux_dir: Option<PathBuf>,
+    pub default_files: Vec<String>,
+}
+
+impl Latexmkrc {
+    pub fn parse(text: &str) -> Self {
+        let mut rc = Self::default();
+        for captures in SCALAR_REGEX.captures_iter(text) {
+            let value = captures
+                .name("double")
+                .or_else(|| captures.name("single"))
+                .or_else(|| captures.name("bare"))
+                .unwrap()
+                .as_str();
+
+            match &captures["name"] {
+                "pdf_mode" => rc.pdf_mode = value.parse().ok(),
+                "out_dir" => rc.out_dir = Some(PathBuf::from(value)),
+                "aux_dir" => rc.aux_dir = Some(PathBuf::from(value)),
+                _ => unreachable!(),
+            }
+        }
+
+        if let Some(captures) = DEFAULT_FILES_REGEX.captures_iter(text).last() {
+            rc.default_files = STRING_REGEX
+                .captures_iter(&captures["files"])
+                .filter_map(|file| file.name("double").or_else(|| file.name("single")))
+                .map(|file| file.as_str().to_owned())
+                .collect();
+        }
+        rc
+    }
+
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            pdf_mode: other.pdf_mode.or(self.pdf_mode),
+            out_dir: other.out_dir.or(self.out_dir),
+            aux_dir: other.aux_dir.or(self.aux_dir),
+            default_files: if other.default_files.is_empty() {
+                self.default_files
+            } else {
+                other.default_files
+            },
+        }
+    }
+
+    pub fn read(directory: &Path) -> Self {
+        Self::read_all(PROJECT_FILE_NAMES.iter().map(|name| directory.join(name)))
+    }
+
+    pub fn load(directory: &Path) -> Self {
+        Self::read_all(Self::user_files()).merge(Self::read(directory))
+    }
+
+    fn read_all<I: IntoIterator<Item = PathBuf>>(paths: I) -> Self {
+        paths
+            .into_iter()
+            .filter_map(|path| Self::read_file(&path))
+            .fold(Self::default(), Self::merge)
+    }
+
+    fn read_file(path: &Path) -> Option<Self> {
+        let modified = fs::metadata(path).and_then(|data| data.modified()).ok()?;
+        let mut cache = FILE_CACHE.lock().unwrap();
+        if let Some((time, rc)) = cache.get(path) {
+            if *time == modified {
+                return Some(rc.clone());
+            }
+        }
+
+        let rc = Self::parse(&fs::read_to_string(path).ok()?);
+        cache.insert(path.to_owned(), (modified, rc.clone()));
+        Some(rc)
+    }
+
+    fn user_files() -> Vec<PathBuf> {
+        let home = match env::var_os("HOME").or_else(|| env::var_os("USERPROFILE")) {
+            Some(home) => PathBuf::from(home),
+            None => return Vec::new(),
+        };
+
+        let config = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".config"));
+
+        vec![
+            config.join("latexmk").join("latexmkrc"),
+            home.join(".latexmkrc"),
+        ]
+    }
+
+    pub fn build_args(&self) -> Option<Vec<String>> {
+        let mode = match self.pdf_mode? {
+            1 => "-pdf",
+            2 => "-pdfps",
+            3 => "-pdfdvi",
+            4 => "-pdflua",
+            5 => "-pdfxe",
+            _ => return None,
+        };
+
+        Some(vec![
+            mode.to_owned(),
+            "-interaction=nonstopmode".to_owned(),
+            "-synctex=1".to_owned(),
+        ])
+    }
+
+    pub fn apply(&self, directory: &Path, mut options: Options) -> Options {
+        if *self == Self::default() {
+            return options;
+        }
+
+        let build = options
+            .latex
+            .get_or_insert_with(LatexOptions::default)
+            .build
+            .get_or_insert_with(LatexBuildOptions::default);
+
+        if build.output_directory.is_none() {
+            build.output_directory = self.out_dir.as_ref().map(|dir| directory.join(dir));
+        }
+
+        if build.aux_directory.is_none() {
+            build.aux_directory = self.aux_dir.as_ref().map(|dir| directory.join(dir));
+        }
+
+        if build.args.is_none() {
+            build.args = self.build_args();
+        }
+        options
+    }
+
+    pub fn project(&self, directory: &Path) -> Option<Project> {
+        let roots: Vec<_> = self
+            .default_files
+            .iter()
+            .filter(|file| !file.contains(|c| c == '*' || c == '?'))
+            .map(|file| {
+                let mut path = directory.join(file);
+                if path.extension().is_none() {
+                    path.set_extension("tex");
+                }
+                path
+            })
+            .filter_map(|path| Uri::from_file_path(path).ok())
+            .collect();
+
+        if roots.is_empty() {
+            None
+        } else {
+            Some(Project {
+                directory: directory.to_owned(),
+                roots,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = r#"
+# build with xelatex
+$pdf_mode = 5;
+$out_dir = 'build';
+$aux_dir = "build/aux";
+@default_files = ('main.tex', "appendix");
+"#;
+
+    #[test]
+    fn parse() {
+        let rc = Latexmkrc::parse(TEXT);
+        assert_eq!(
+            rc,
+            Latexmkrc {
+                pdf_mode: Some(5),
+                out_dir: Some(PathBuf::from("build")),
+                aux_dir: Some(PathBuf::from("build/aux")),
+                default_files: vec!["main.tex".into(), "appendix".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_comment() {
+        let rc = Latexmkrc::parse("# $pdf_mode = 4;\n$out_dir = out; # comment");
+        assert_eq!(rc.pdf_mode, None);
+        assert_eq!(rc.out_dir, Some(PathBuf::from("out")));
+    }
+
+    #[test]
+    fn merge() {
+        let user = Latexmkrc::parse("$pdf_mode = 4;\n$out_dir = 'out';");
+        let project = Latexmkrc::parse("$pdf_mode = 1;");
+        let rc = user.merge(project);
+        assert_eq!(rc.pdf_mode, Some(1));
+        assert_eq!(rc.out_dir, Some(PathBuf::from("out")));
+    }
+
+    #[test]
+    fn apply() {
+        let directory = env::temp_dir();
+        let options = Latexmkrc::parse(TEXT).apply(&directory, Options::default());
+        let build = options.latex.unwrap().build.unwrap();
+        assert_eq!(build.output_directory, Some(directory.join("build")));
+        assert_eq!(build.aux_directory, Some(directory.join("build/aux")));
+        assert_eq!(build.args.unwrap()[0], "-pdfxe");
+    }
+
+    #[test]
+    fn apply_keep_settings() {
+        let directory = env::temp_dir();
+        let options = Options {
+            latex: Some(LatexOptions {
+                build: Some(LatexBuildOptions {
+                    args: Some(Vec::new()),
+                    output_directory: Some(PathBuf::from("foo")),
+                    ..LatexBuildOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        };
+        let options = Latexmkrc::parse(TEXT).apply(&directory, options);
+        let build = options.latex.unwrap().build.unwrap();
+        assert_eq!(build.output_directory, Some(PathBuf::from("foo")));
+        assert_eq!(build.args, Some(Vec::new()));
+    }
+
+    #[test]
+    fn project() {
+        let directory = env::temp_dir();
+        let project = Latexmkrc::parse(TEXT).project(&directory).unwrap();
+        assert_eq!(
+            project.roots,
+            vec![
+                Uri::from_file_path(directory.join("main.tex")).unwrap(),
+                Uri::from_file_path(directory.join("appendix.tex")).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_cached() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("latexmkrc");
+        fs::write(&path, TEXT).unwrap();
+        assert_eq!(Latexmkrc::read(directory.path()), Latexmkrc::parse(TEXT));
+
+        let cache = FILE_CACHE.lock().unwrap();
+        assert_eq!(cache[&path].1, Latexmkrc::parse(TEXT));
+    }
+}
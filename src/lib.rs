@@ -2,12 +2,14 @@
 
 pub mod action;
 pub mod build;
+pub mod code_action;
 pub mod config;
 pub mod definition;
 pub mod diagnostics;
 pub mod folding;
 pub mod forward_search;
 pub mod highlight;
+pub mod latexmkrc;
 pub mod link;
 pub mod reference;
 pub mod rename;
@@ -1,4 +1,5 @@
 use futures_boxed::boxed;
+use std::path::PathBuf;
 use texlab_protocol::{DocumentLink, DocumentLinkParams};
 use texlab_syntax::*;
 use texlab_workspace::*;
@@ -16,10 +17,21 @@ impl FeatureProvider for LatexIncludeLinkProvider {
         request: &'a FeatureRequest<DocumentLinkParams>,
     ) -> Vec<DocumentLink> {
         if let SyntaxTree::Latex(tree) = &request.document().tree {
+            let directories = graphics_directories(
+                request.workspace(),
+                &request.document().uri,
+                &request.options,
+            );
             return tree
                 .includes
                 .iter()
-                .flat_map(|include| Self::resolve(request, include))
+                .flat_map(|include| {
+                    if include.kind.is_graphics() {
+                        Self::resolve_graphics(include, &directories)
+                    } else {
+                        Self::resolve(request, include)
+                    }
+                })
                 .collect();
         }
         Vec::new()
@@ -49,13 +61,36 @@ impl LatexIncludeLinkProvider {
         }
         links
     }
+
+    fn resolve_graphics(include: &LatexInclude, directories: &[PathBuf]) -> Vec<DocumentLink> {
+        let mut links = Vec::new();
+        let paths = include.paths();
+        for (i, targets) in graphics_targets(include, directories).iter().enumerate() {
+            let target = targets.iter().find(|target| {
+                target
+                    .to_file_path()
+                    .map(|path| path.is_file())
+                    .unwrap_or(false)
+            });
+
+            if let Some(target) = target {
+                links.push(DocumentLink {
+                    range: paths[i].range(),
+                    target: target.clone().into(),
+                });
+            }
+        }
+        links
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use tempfile::tempdir;
     use texlab_protocol::RangeExt;
-    use texlab_protocol::{Position, Range};
+    use texlab_protocol::{Position, Range, Uri};
 
     #[test]
     fn has_links() {
@@ -80,6 +115,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn graphics_path() {
+        let directory = tempdir().unwrap();
+        let path = directory.path().join("baz.png");
+        fs::write(&path, "").unwrap();
+
+        let text = format!(
+            "\\graphicspath{{{{{}/}}}}\n\\includegraphics{{baz}}",
+            directory.path().to_string_lossy().replace('\\', "/")
+        );
+        let links = test_feature(
+            LatexIncludeLinkProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", text)],
+                main_file: "foo.tex",
+                position: Position::new(0, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            links,
+            vec![DocumentLink {
+                range: Range::new_simple(1, 17, 1, 20),
+                target: Uri::from_file_path(path).unwrap().into(),
+            }]
+        );
+    }
+
     #[test]
     fn no_links_latex() {
         let links = test_feature(
@@ -3,6 +3,7 @@ use futures::channel::mpsc;
 use futures::prelude::*;
 use jsonrpc::MessageHandler;
 use std::error::Error;
+use std::path::PathBuf;
 use std::sync::Arc;
 use stderrlog::{ColorChoice, Timestamp};
 use texlab::server::LatexLspServer;
@@ -26,6 +27,13 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 .short("q")
                 .help("No output printed to stderr"),
         )
+        .arg(
+            Arg::with_name("cache-dir")
+                .long("cache-dir")
+                .value_name("DIR")
+                .takes_value(true)
+                .help("Directory to cache the TeX distribution index and parsed documents"),
+        )
         .get_matches();
 
     stderrlog::new()
@@ -49,10 +57,12 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let mut stdin = FramedRead::new(tokio::io::stdin(), LspCodec);
     let (stdout_tx, mut stdout_rx) = mpsc::channel(0);
 
+    let cache_directory = matches.value_of_os("cache-dir").map(PathBuf::from);
     let client = Arc::new(LatexLspClient::new(stdout_tx.clone()));
     let server = Arc::new(LatexLspServer::new(
         Arc::clone(&client),
-        Arc::new(Distribution::detect().await),
+        Arc::new(Distribution::detect(cache_directory.clone()).await),
+        cache_directory,
     ));
     let mut handler = MessageHandler {
         server,
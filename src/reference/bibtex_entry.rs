@@ -38,6 +38,16 @@ impl FeatureProvider for BibtexEntryReferenceProvider {
                                 }
                             }
                         }
+
+                        tree.entries()
+                            .into_iter()
+                            .flat_map(|entry| entry.fields.iter())
+                            .flat_map(BibtexField::crossref_keys)
+                            .filter(|crossref| crossref.text() == key)
+                            .map(|crossref| {
+                                Location::new(document.uri.clone().into(), crossref.range())
+                            })
+                            .for_each(|location| references.push(location));
                     }
                 }
             }
@@ -68,6 +78,17 @@ impl BibtexEntryReferenceProvider {
                             return Some(key.text());
                         }
                     }
+
+                    for field in &entry.fields {
+                        for crossref in field.crossref_keys() {
+                            if crossref
+                                .range()
+                                .contains(request.params.text_document_position.position)
+                            {
+                                return Some(crossref.text());
+                            }
+                        }
+                    }
                 }
                 None
             }
@@ -181,6 +202,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn crossref() {
+        let references = test_feature(
+            BibtexEntryReferenceProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.bib",
+                        "@proceedings{foo,}\n@inproceedings{bar, crossref = {foo}}",
+                    ),
+                    FeatureSpec::file("bar.tex", "\\addbibresource{foo.bib}\n\\cite{foo}"),
+                ],
+                main_file: "foo.bib",
+                position: Position::new(0, 14),
+                include_declaration: false,
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            references,
+            vec![
+                Location::new(FeatureSpec::uri("foo.bib"), Range::new_simple(1, 32, 1, 35)),
+                Location::new(FeatureSpec::uri("bar.tex"), Range::new_simple(1, 6, 1, 9)),
+            ]
+        );
+    }
+
+    #[test]
+    fn crossref_field() {
+        let references = test_feature(
+            BibtexEntryReferenceProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@proceedings{foo,}\n@inproceedings{bar, crossref = {foo}}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(1, 33),
+                include_declaration: true,
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            references,
+            vec![
+                Location::new(FeatureSpec::uri("foo.bib"), Range::new_simple(0, 13, 0, 16)),
+                Location::new(FeatureSpec::uri("foo.bib"), Range::new_simple(1, 32, 1, 35)),
+            ]
+        );
+    }
+
     #[test]
     fn empty() {
         let references = test_feature(
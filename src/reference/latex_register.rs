@@ -0,0 +1,119 @@
+use futures_boxed::boxed;
+use texlab_protocol::RangeExt;
+use texlab_protocol::{Location, ReferenceParams};
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexRegisterReferenceProvider;
+
+impl FeatureProvider for LatexRegisterReferenceProvider {
+    type Params = ReferenceParams;
+    type Output = Vec<Location>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<ReferenceParams>) -> Vec<Location> {
+        let mut references = Vec::new();
+        if let Some(definition) = Self::find_register(request) {
+            for document in request.related_documents() {
+                if let SyntaxTree::Latex(tree) = &document.tree {
+                    tree.registers
+                        .registers
+                        .iter()
+                        .filter(|register| register.kind == definition.kind)
+                        .filter(|register| {
+                            !register.definition || request.params.context.include_declaration
+                        })
+                        .map(LatexRegister::name)
+                        .filter(|name| name.text() == definition.name().text())
+                        .map(|name| Location::new(document.uri.clone().into(), name.range()))
+                        .for_each(|location| references.push(location))
+                }
+            }
+        }
+        references
+    }
+}
+
+impl LatexRegisterReferenceProvider {
+    fn find_register(request: &FeatureRequest<ReferenceParams>) -> Option<&LatexRegister> {
+        if let SyntaxTree::Latex(tree) = &request.document().tree {
+            tree.registers.registers.iter().find(|register| {
+                register
+                    .name()
+                    .range()
+                    .contains(request.params.text_document_position.position)
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Position, Range};
+
+    #[test]
+    fn counter() {
+        let references = test_feature(
+            LatexRegisterReferenceProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.tex", "\\newcounter{foo}\n\\include{bar}"),
+                    FeatureSpec::file("bar.tex", "\\stepcounter{foo}\n\\arabic{foo}"),
+                ],
+                main_file: "foo.tex",
+                include_declaration: false,
+                position: Position::new(0, 13),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            references,
+            vec![
+                Location::new(FeatureSpec::uri("bar.tex"), Range::new_simple(0, 13, 0, 16)),
+                Location::new(FeatureSpec::uri("bar.tex"), Range::new_simple(1, 8, 1, 11)),
+            ]
+        );
+    }
+
+    #[test]
+    fn length_include_declaration() {
+        let references = test_feature(
+            LatexRegisterReferenceProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\newlength{\\foo}\n\\newsavebox{\\foo}\n\\setlength{\\foo}{1cm}",
+                )],
+                main_file: "foo.tex",
+                include_declaration: true,
+                position: Position::new(2, 13),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            references,
+            vec![
+                Location::new(FeatureSpec::uri("foo.tex"), Range::new_simple(0, 11, 0, 15)),
+                Location::new(FeatureSpec::uri("foo.tex"), Range::new_simple(2, 11, 2, 15)),
+            ]
+        );
+    }
+
+    #[test]
+    fn bibtex() {
+        let references = test_feature(
+            LatexRegisterReferenceProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "")],
+                main_file: "foo.bib",
+                position: Position::new(0, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(references.is_empty());
+    }
+}
@@ -1,10 +1,12 @@
 mod bibtex_entry;
 mod bibtex_string;
 mod latex_label;
+mod latex_register;
 
 use self::bibtex_entry::BibtexEntryReferenceProvider;
 use self::bibtex_string::BibtexStringReferenceProvider;
 use self::latex_label::LatexLabelReferenceProvider;
+use self::latex_register::LatexRegisterReferenceProvider;
 use futures_boxed::boxed;
 use texlab_protocol::{Location, ReferenceParams};
 use texlab_workspace::*;
@@ -20,6 +22,7 @@ impl ReferenceProvider {
                 Box::new(BibtexEntryReferenceProvider),
                 Box::new(BibtexStringReferenceProvider),
                 Box::new(LatexLabelReferenceProvider),
+                Box::new(LatexRegisterReferenceProvider),
             ]),
         }
     }
@@ -1,5 +1,6 @@
 use futures_boxed::boxed;
 use std::collections::HashMap;
+use std::sync::Arc;
 use texlab_protocol::RangeExt;
 use texlab_protocol::*;
 use texlab_syntax::*;
@@ -37,37 +38,49 @@ impl FeatureProvider for BibtexEntryRenameProvider {
             &request.document().tree,
             request.params.text_document_position.position,
         )?;
-        let mut changes = HashMap::new();
-        for document in request.related_documents() {
-            let mut edits = Vec::new();
-            match &document.tree {
-                SyntaxTree::Latex(tree) => {
-                    tree.citations
-                        .iter()
-                        .flat_map(LatexCitation::keys)
-                        .filter(|citation| citation.text() == key_name.text)
-                        .map(|citation| {
-                            TextEdit::new(citation.range(), request.params.new_name.clone())
-                        })
-                        .for_each(|edit| edits.push(edit));
-                }
-                SyntaxTree::Bibtex(tree) => {
-                    for entry in tree.entries() {
-                        if let Some(key) = &entry.key {
-                            if key.text() == key_name.text {
-                                edits.push(TextEdit::new(
-                                    key.range(),
-                                    request.params.new_name.clone(),
-                                ));
-                            }
+        let edit = rename_key(
+            request.related_documents(),
+            &key_name.text,
+            &request.params.new_name,
+        );
+        Some(edit)
+    }
+}
+
+pub fn rename_key(documents: &[Arc<Document>], old_key: &str, new_key: &str) -> WorkspaceEdit {
+    let mut changes = HashMap::new();
+    for document in documents {
+        let mut edits = Vec::new();
+        match &document.tree {
+            SyntaxTree::Latex(tree) => {
+                tree.citations
+                    .iter()
+                    .flat_map(LatexCitation::keys)
+                    .filter(|citation| citation.text() == old_key)
+                    .map(|citation| TextEdit::new(citation.range(), new_key.to_owned()))
+                    .for_each(|edit| edits.push(edit));
+            }
+            SyntaxTree::Bibtex(tree) => {
+                for entry in tree.entries() {
+                    if let Some(key) = &entry.key {
+                        if key.text() == old_key {
+                            edits.push(TextEdit::new(key.range(), new_key.to_owned()));
                         }
                     }
+
+                    entry
+                        .fields
+                        .iter()
+                        .flat_map(BibtexField::crossref_keys)
+                        .filter(|key| key.text() == old_key)
+                        .map(|key| TextEdit::new(key.range(), new_key.to_owned()))
+                        .for_each(|edit| edits.push(edit));
                 }
-            };
-            changes.insert(document.uri.clone().into(), edits);
-        }
-        Some(WorkspaceEdit::new(changes))
+            }
+        };
+        changes.insert(document.uri.clone().into(), edits);
     }
+    WorkspaceEdit::new(changes)
 }
 
 fn find_key(tree: &SyntaxTree, position: Position) -> Option<&Span> {
@@ -90,6 +103,14 @@ fn find_key(tree: &SyntaxTree, position: Position) -> Option<&Span> {
                         return Some(&key.span);
                     }
                 }
+
+                for field in &entry.fields {
+                    for key in field.crossref_keys() {
+                        if key.range().contains(position) {
+                            return Some(&key.span);
+                        }
+                    }
+                }
             }
             None
         }
@@ -169,4 +190,30 @@ mod tests {
         );
         assert_eq!(edit, None);
     }
+
+    #[test]
+    fn crossref() {
+        let edit = test_feature(
+            BibtexEntryRenameProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@inproceedings{foo, crossref = {bar}}\n@proceedings{bar,}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 32),
+                new_name: "qux",
+                ..FeatureSpec::default()
+            },
+        );
+        let mut changes = HashMap::new();
+        changes.insert(
+            FeatureSpec::uri("foo.bib"),
+            vec![
+                TextEdit::new(Range::new_simple(0, 32, 0, 35), "qux".into()),
+                TextEdit::new(Range::new_simple(1, 13, 1, 16), "qux".into()),
+            ],
+        );
+        assert_eq!(edit, Some(WorkspaceEdit::new(changes)));
+    }
 }
@@ -18,6 +18,10 @@ impl FeatureProvider for LatexEnvironmentPrepareRenameProvider {
         request: &'a FeatureRequest<TextDocumentPositionParams>,
     ) -> Option<Range> {
         let position = request.params.position;
+        if let Some(name) = find_definition(&request.document().tree, position) {
+            return Some(name.range());
+        }
+
         let environment = find_environment(&request.document().tree, position)?;
         let left_range = environment.left.name().unwrap().range();
         let right_range = environment.right.name().unwrap().range();
@@ -41,10 +45,12 @@ impl FeatureProvider for LatexEnvironmentRenameProvider {
         &'a self,
         request: &'a FeatureRequest<RenameParams>,
     ) -> Option<WorkspaceEdit> {
-        let environment = find_environment(
-            &request.document().tree,
-            request.params.text_document_position.position,
-        )?;
+        let position = request.params.text_document_position.position;
+        if let Some(edit) = rename_user_environment(request, position) {
+            return Some(edit);
+        }
+
+        let environment = find_environment(&request.document().tree, position)?;
         let edits = vec![
             TextEdit::new(
                 environment.left.name().unwrap().range(),
@@ -61,6 +67,70 @@ impl FeatureProvider for LatexEnvironmentRenameProvider {
     }
 }
 
+fn rename_user_environment(
+    request: &FeatureRequest<RenameParams>,
+    position: Position,
+) -> Option<WorkspaceEdit> {
+    let tree = &request.document().tree;
+    let name = match find_definition(tree, position) {
+        Some(name) => name.text(),
+        None => {
+            let environment = find_environment(tree, position)?;
+            let left_name = environment.left.name().unwrap();
+            let right_name = environment.right.name().unwrap();
+            if left_name.text() != right_name.text() {
+                return None;
+            }
+            left_name.text()
+        }
+    };
+
+    let is_defined = request.related_documents().iter().any(|document| {
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            tree.environment_definitions
+                .iter()
+                .any(|def| def.name().text() == name)
+        } else {
+            false
+        }
+    });
+
+    if !is_defined {
+        return None;
+    }
+
+    let mut changes = HashMap::new();
+    for document in request.related_documents() {
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            let definitions = tree.environment_definitions.iter().map(|def| def.name());
+            let delimiters = tree
+                .env
+                .environments
+                .iter()
+                .flat_map(|env| env.left.name().into_iter().chain(env.right.name()));
+
+            let edits: Vec<TextEdit> = definitions
+                .chain(delimiters)
+                .filter(|token| token.text() == name)
+                .map(|token| TextEdit::new(token.range(), request.params.new_name.clone()))
+                .collect();
+            changes.insert(document.uri.clone().into(), edits);
+        }
+    }
+    Some(WorkspaceEdit::new(changes))
+}
+
+fn find_definition(tree: &SyntaxTree, position: Position) -> Option<&LatexToken> {
+    if let SyntaxTree::Latex(tree) = &tree {
+        tree.environment_definitions
+            .iter()
+            .map(LatexEnvironmentDefinition::name)
+            .find(|name| name.range().contains(position))
+    } else {
+        None
+    }
+}
+
 fn find_environment(tree: &SyntaxTree, position: Position) -> Option<&LatexEnvironment> {
     if let SyntaxTree::Latex(tree) = &tree {
         for environment in &tree.env.environments {
@@ -105,6 +175,66 @@ mod tests {
         assert_eq!(edit, Some(WorkspaceEdit::new(changes)));
     }
 
+    #[test]
+    fn user_environment() {
+        let edit = test_feature(
+            LatexEnvironmentRenameProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.tex",
+                        "\\NewDocumentEnvironment{foo}{}{}{}\n\\include{bar}",
+                    ),
+                    FeatureSpec::file("bar.tex", "\\begin{foo}\n\\end{foo}"),
+                ],
+                main_file: "bar.tex",
+                position: Position::new(0, 8),
+                new_name: "baz",
+                ..FeatureSpec::default()
+            },
+        );
+        let mut changes = HashMap::new();
+        changes.insert(
+            FeatureSpec::uri("foo.tex"),
+            vec![TextEdit::new(Range::new_simple(0, 24, 0, 27), "baz".into())],
+        );
+        changes.insert(
+            FeatureSpec::uri("bar.tex"),
+            vec![
+                TextEdit::new(Range::new_simple(0, 7, 0, 10), "baz".into()),
+                TextEdit::new(Range::new_simple(1, 5, 1, 8), "baz".into()),
+            ],
+        );
+        assert_eq!(edit, Some(WorkspaceEdit::new(changes)));
+    }
+
+    #[test]
+    fn user_environment_definition() {
+        let edit = test_feature(
+            LatexEnvironmentRenameProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\newenvironment{foo}{}{}\n\\begin{foo}\\end{foo}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(0, 17),
+                new_name: "bar",
+                ..FeatureSpec::default()
+            },
+        );
+        let mut changes = HashMap::new();
+        changes.insert(
+            FeatureSpec::uri("foo.tex"),
+            vec![
+                TextEdit::new(Range::new_simple(0, 16, 0, 19), "bar".into()),
+                TextEdit::new(Range::new_simple(1, 7, 1, 10), "bar".into()),
+                TextEdit::new(Range::new_simple(1, 16, 1, 19), "bar".into()),
+            ],
+        );
+        assert_eq!(edit, Some(WorkspaceEdit::new(changes)));
+    }
+
     #[test]
     fn command() {
         let edit = test_feature(
@@ -7,6 +7,8 @@ use self::bibtex_entry::*;
 use self::latex_command::*;
 use self::latex_environment::*;
 use self::latex_label::*;
+
+pub use self::bibtex_entry::rename_key;
 use futures_boxed::boxed;
 use texlab_protocol::*;
 use texlab_workspace::*;
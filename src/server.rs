@@ -1,11 +1,13 @@
 use crate::action::{Action, ActionManager, LintReason};
 use crate::build::*;
+use crate::code_action::CodeActionProvider;
 use crate::config::ConfigStrategy;
 use crate::definition::DefinitionProvider;
 use crate::diagnostics::DiagnosticsManager;
 use crate::folding::FoldingProvider;
 use crate::forward_search;
 use crate::highlight::HighlightProvider;
+use crate::latexmkrc::Latexmkrc;
 use crate::link::LinkProvider;
 use crate::reference::ReferenceProvider;
 use crate::rename::{PrepareRenameProvider, RenameProvider};
@@ -16,29 +18,43 @@ use jsonrpc::server::{Middleware, Result};
 use jsonrpc_derive::{jsonrpc_method, jsonrpc_server};
 use log::*;
 use once_cell::sync::{Lazy, OnceCell};
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fs;
 use std::future::Future;
+use std::path::PathBuf;
 use std::sync::Arc;
 use texlab_citeproc::render_citation;
-use texlab_completion::{CompletionItemData, CompletionProvider};
+use texlab_completion::{CompletionItemData, CompletionProvider, COMPLETION_ACCEPTED_COMMAND};
 use texlab_distro::{Distribution, DistributionKind, Language};
 use texlab_hover::HoverProvider;
 use texlab_protocol::*;
 use texlab_symbol::SymbolProvider;
 use texlab_syntax::*;
 use texlab_workspace::*;
+use uuid::Uuid;
 use walkdir::WalkDir;
 
+#[derive(Debug, Default)]
+struct IndexProgress {
+    token: Option<ProgressToken>,
+    started: bool,
+    done: usize,
+    total: usize,
+}
+
 pub struct LatexLspServer<C> {
     client: Arc<C>,
     client_capabilities: OnceCell<Arc<ClientCapabilities>>,
     distribution: Arc<Box<dyn Distribution>>,
     config_strategy: OnceCell<Box<dyn ConfigStrategy>>,
+    workspace_directory: OnceCell<PathBuf>,
     build_manager: BuildManager<C>,
     workspace_manager: WorkspaceManager,
+    index_lock: Mutex<()>,
     action_manager: ActionManager,
     diagnostics_manager: Mutex<DiagnosticsManager>,
+    code_action_provider: CodeActionProvider,
     completion_provider: CompletionProvider,
     definition_provider: DefinitionProvider,
     folding_provider: FoldingProvider,
@@ -53,16 +69,23 @@ pub struct LatexLspServer<C> {
 
 #[jsonrpc_server]
 impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
-    pub fn new(client: Arc<C>, distribution: Arc<Box<dyn Distribution>>) -> Self {
+    pub fn new(
+        client: Arc<C>,
+        distribution: Arc<Box<dyn Distribution>>,
+        cache_directory: Option<PathBuf>,
+    ) -> Self {
         Self {
             client: Arc::clone(&client),
             client_capabilities: OnceCell::new(),
             distribution: Arc::clone(&distribution),
             config_strategy: OnceCell::new(),
+            workspace_directory: OnceCell::new(),
             build_manager: BuildManager::new(client),
-            workspace_manager: WorkspaceManager::new(distribution),
+            workspace_manager: WorkspaceManager::new(distribution, cache_directory),
+            index_lock: Mutex::new(()),
             action_manager: ActionManager::default(),
             diagnostics_manager: Mutex::new(DiagnosticsManager::default()),
+            code_action_provider: CodeActionProvider::new(),
             completion_provider: CompletionProvider::new(),
             definition_provider: DefinitionProvider::new(),
             folding_provider: FoldingProvider::new(),
@@ -93,6 +116,10 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         let config_strategy = ConfigStrategy::select(&params.capabilities, client);
         let _ = self.config_strategy.set(config_strategy);
 
+        if let Some(path) = params.root_uri.and_then(|uri| uri.to_file_path().ok()) {
+            let _ = self.workspace_directory.set(path);
+        }
+
         self.client_capabilities
             .set(Arc::new(params.capabilities))
             .unwrap();
@@ -128,10 +155,10 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             document_highlight_provider: Some(true),
             document_symbol_provider: Some(true),
             workspace_symbol_provider: Some(true),
-            code_action_provider: None,
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
             code_lens_provider: None,
             document_formatting_provider: Some(true),
-            document_range_formatting_provider: None,
+            document_range_formatting_provider: Some(true),
             document_on_type_formatting_provider: None,
             rename_provider: Some(RenameProviderCapability::Options(RenameOptions {
                 prepare_provider: Some(true),
@@ -141,7 +168,9 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             }),
             color_provider: None,
             folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
-            execute_command_provider: None,
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: vec![COMPLETION_ACCEPTED_COMMAND.into()],
+            }),
             workspace: None,
             selection_range_provider: None,
         };
@@ -173,7 +202,9 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     pub async fn did_open(&self, params: DidOpenTextDocumentParams) {
         let uri = params.text_document.uri.clone();
         let options = self.configuration(false).await;
-        self.workspace_manager.add(params.text_document, &options);
+        self.workspace_manager
+            .add(params.text_document, &options)
+            .await;
         self.action_manager
             .push(Action::DetectRoot(uri.clone().into()));
         self.action_manager
@@ -187,7 +218,8 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         for change in params.content_changes {
             let uri = params.text_document.uri.clone();
             self.workspace_manager
-                .update(uri.into(), change.text, &options);
+                .update(uri.into(), change.text, &options)
+                .await;
         }
         self.action_manager.push(Action::RunLinter(
             params.text_document.uri.into(),
@@ -256,6 +288,16 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         Ok(item)
     }
 
+    #[jsonrpc_method("workspace/executeCommand", kind = "request")]
+    pub async fn execute_command(&self, params: ExecuteCommandParams) -> Result<()> {
+        if params.command == COMPLETION_ACCEPTED_COMMAND {
+            if let Some(label) = params.arguments.get(0).and_then(|arg| arg.as_str()) {
+                self.completion_provider.accept(label.to_owned());
+            }
+        }
+        Ok(())
+    }
+
     #[jsonrpc_method("textDocument/hover", kind = "request")]
     pub async fn hover(&self, params: TextDocumentPositionParams) -> Result<Option<Hover>> {
         let request = self
@@ -357,38 +399,36 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         Ok(links)
     }
 
-    #[jsonrpc_method("textDocument/formatting", kind = "request")]
-    pub async fn formatting(&self, params: DocumentFormattingParams) -> Result<Vec<TextEdit>> {
+    #[jsonrpc_method("textDocument/codeAction", kind = "request")]
+    pub async fn code_action(&self, params: CodeActionParams) -> Result<Vec<CodeActionOrCommand>> {
         let request = self
             .make_feature_request(params.text_document.as_uri(), params)
             .await?;
-        let mut edits = Vec::new();
-        if let SyntaxTree::Bibtex(tree) = &request.document().tree {
-            let options = self
-                .configuration(true)
-                .await
-                .bibtex
-                .and_then(|opts| opts.formatting)
-                .unwrap_or_default();
+        let actions = self.code_action_provider.execute(&request).await;
+        Ok(actions
+            .into_iter()
+            .map(CodeActionOrCommand::CodeAction)
+            .collect())
+    }
 
-            let params = BibtexFormattingParams {
-                tab_size: request.params.options.tab_size as usize,
-                insert_spaces: request.params.options.insert_spaces,
-                options,
-            };
+    #[jsonrpc_method("textDocument/formatting", kind = "request")]
+    pub async fn formatting(&self, params: DocumentFormattingParams) -> Result<Vec<TextEdit>> {
+        let request = self
+            .make_feature_request(params.text_document.as_uri(), params.options)
+            .await?;
+        let edits = self.format_bibtex(&request, None).await;
+        Ok(edits)
+    }
 
-            for declaration in &tree.root.children {
-                let should_format = match declaration {
-                    BibtexDeclaration::Comment(_) => false,
-                    BibtexDeclaration::Preamble(_) | BibtexDeclaration::String(_) => true,
-                    BibtexDeclaration::Entry(entry) => !entry.is_comment(),
-                };
-                if should_format {
-                    let text = format_declaration(&declaration, &params);
-                    edits.push(TextEdit::new(declaration.range(), text));
-                }
-            }
-        }
+    #[jsonrpc_method("textDocument/rangeFormatting", kind = "request")]
+    pub async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Vec<TextEdit>> {
+        let request = self
+            .make_feature_request(params.text_document.as_uri(), params.options)
+            .await?;
+        let edits = self.format_bibtex(&request, Some(params.range)).await;
         Ok(edits)
     }
 
@@ -425,7 +465,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         let request = self
             .make_feature_request(params.text_document.as_uri(), params)
             .await?;
-        let options = self.configuration(true).await.latex.unwrap_or_default();
+        let options = request.options.latex.clone().unwrap_or_default();
         let result = self.build_manager.build(request, options).await;
         Ok(result)
     }
@@ -438,7 +478,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         let request = self
             .make_feature_request(params.text_document.as_uri(), params)
             .await?;
-        let options = self.configuration(true).await;
+        let options = request.options.clone();
 
         match request.document().uri.to_file_path() {
             Ok(tex_file) => {
@@ -457,7 +497,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         }
     }
 
-    async fn configuration(&self, fetch: bool) -> Options {
+    async fn client_configuration(&self, fetch: bool) -> Options {
         if let Some(strategy) = self.config_strategy.get() {
             strategy.get(fetch).await
         } else {
@@ -465,6 +505,111 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         }
     }
 
+    fn root_directory(&self, options: &Options) -> Option<PathBuf> {
+        options
+            .latex
+            .as_ref()
+            .and_then(|latex| latex.root_directory.clone())
+            .or_else(|| self.workspace_directory.get().cloned())
+    }
+
+    fn apply_latexmkrc(&self, directory: Option<PathBuf>, options: Options) -> Options {
+        match directory {
+            Some(directory) => Latexmkrc::load(&directory).apply(&directory, options),
+            None => options,
+        }
+    }
+
+    async fn configuration(&self, fetch: bool) -> Options {
+        let options = self.client_configuration(fetch).await;
+        self.apply_latexmkrc(self.root_directory(&options), options)
+    }
+
+    async fn document_configuration(&self, uri: &Uri, fetch: bool) -> Options {
+        let options = self.client_configuration(fetch).await;
+        let has_root_directory = options
+            .latex
+            .as_ref()
+            .map_or(false, |latex| latex.root_directory.is_some());
+
+        let project_directory = if has_root_directory {
+            None
+        } else {
+            self.workspace_manager
+                .get()
+                .find_project(uri)
+                .map(|project| project.directory.clone())
+        };
+
+        match project_directory {
+            Some(directory) => self.apply_latexmkrc(Some(directory), options),
+            None => self.apply_latexmkrc(self.root_directory(&options), options),
+        }
+    }
+
+    async fn update_workspace_options(&self) {
+        let options = self.client_configuration(false).await;
+        let mut project_options = HashMap::new();
+        let has_root_directory = options
+            .latex
+            .as_ref()
+            .map_or(false, |latex| latex.root_directory.is_some());
+        if !has_root_directory {
+            for project in self.workspace_manager.get().projects() {
+                let directory = project.directory.clone();
+                let value = self.apply_latexmkrc(Some(directory.clone()), options.clone());
+                project_options.insert(directory, value);
+            }
+        }
+
+        let workspace_options = self.apply_latexmkrc(self.root_directory(&options), options);
+        self.workspace_manager
+            .update_options(&workspace_options, project_options);
+    }
+
+    async fn format_bibtex(
+        &self,
+        request: &FeatureRequest<FormattingOptions>,
+        range: Option<Range>,
+    ) -> Vec<TextEdit> {
+        let mut edits = Vec::new();
+        if let SyntaxTree::Bibtex(tree) = &request.document().tree {
+            let options = self
+                .configuration(true)
+                .await
+                .bibtex
+                .and_then(|opts| opts.formatting)
+                .unwrap_or_default();
+
+            let params = BibtexFormattingParams {
+                tab_size: request.params.tab_size as usize,
+                insert_spaces: request.params.insert_spaces,
+                options,
+            };
+
+            if range.is_none() && params.options.sort_entries.is_some() {
+                let text = &request.document().text;
+                let range = Range::new(Position::new(0, 0), CharStream::end_position(text));
+                let text = format_bibliography(text, tree, &params);
+                return vec![TextEdit::new(range, text)];
+            }
+
+            for declaration in &tree.root.children {
+                let should_format = match declaration {
+                    BibtexDeclaration::Comment(_) => false,
+                    BibtexDeclaration::Preamble(_) | BibtexDeclaration::String(_) => true,
+                    BibtexDeclaration::Entry(entry) => !entry.is_comment(),
+                };
+                let in_range = range.map_or(true, |range| declaration.range().intersects(range));
+                if should_format && in_range {
+                    let text = format_declaration(&declaration, &params);
+                    edits.push(TextEdit::new(declaration.range(), text));
+                }
+            }
+        }
+        edits
+    }
+
     async fn make_feature_request<P>(&self, uri: Uri, params: P) -> Result<FeatureRequest<P>> {
         let workspace = self.workspace_manager.get();
         let client_capabilities = self
@@ -473,7 +618,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             .expect("Failed to retrieve client capabilities");
 
         if let Some(document) = workspace.find(&uri) {
-            let options = self.configuration(true).await;
+            let options = self.document_configuration(&uri, true).await;
             Ok(FeatureRequest {
                 params,
                 view: DocumentView::new(workspace, document, &options),
@@ -487,25 +632,106 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         }
     }
 
-    async fn detect_children(&self) {
-        let options = self.configuration(false).await;
+    async fn detect_children(&self, options: &Options, progress: &mut IndexProgress) {
         loop {
-            let mut changed = false;
-
-            let workspace = self.workspace_manager.get();
-            for path in workspace.unresolved_includes(&options) {
-                if path.exists() {
-                    changed |= self.workspace_manager.load(&path, &options).is_ok();
-                }
-            }
+            let paths: Vec<_> = {
+                let workspace = self.workspace_manager.get();
+                workspace
+                    .unresolved_includes(options)
+                    .into_iter()
+                    .filter(|path| path.exists())
+                    .collect()
+            };
 
-            if !changed {
+            if self.index(paths, options, progress).await == 0 {
                 break;
             }
         }
     }
 
-    fn update_document(
+    async fn index(
+        &self,
+        paths: Vec<PathBuf>,
+        options: &Options,
+        progress: &mut IndexProgress,
+    ) -> usize {
+        if paths.is_empty() {
+            return 0;
+        }
+
+        progress.total += paths.len();
+        if !progress.started {
+            progress.started = true;
+            progress.token = self.begin_progress(progress.total).await;
+        }
+
+        let offset = progress.done;
+        let total = progress.total;
+        let token = progress.token.as_ref();
+        let batch_size = paths.len();
+        let count = self
+            .workspace_manager
+            .load_all(paths, options, |done, _| async move {
+                if let Some(token) = token {
+                    let done = offset + done;
+                    let progress = WorkDoneProgress::Report(WorkDoneProgressReport {
+                        cancellable: Some(false),
+                        message: Some(format!("{}/{} files", done, total)),
+                        percentage: Some(done as f64 * 100.0 / total as f64),
+                    });
+                    self.report_progress(token, progress).await;
+                }
+            })
+            .await;
+
+        progress.done += batch_size;
+        count
+    }
+
+    async fn begin_progress(&self, total: usize) -> Option<ProgressToken> {
+        let has_progress_support = self
+            .client_capabilities
+            .get()
+            .map_or(false, |capabilities| {
+                capabilities.has_work_done_progress_support()
+            });
+
+        if !has_progress_support {
+            return None;
+        }
+
+        let token = ProgressToken::String(format!("texlab-index-{}", Uuid::new_v4()));
+        let params = WorkDoneProgressCreateParams {
+            token: token.clone(),
+        };
+        self.client.work_done_progress_create(params).await.ok()?;
+
+        let progress = WorkDoneProgress::Begin(WorkDoneProgressBegin {
+            title: "Indexing".into(),
+            cancellable: Some(false),
+            message: Some(format!("0/{} files", total)),
+            percentage: Some(0.0),
+        });
+        self.report_progress(&token, progress).await;
+        Some(token)
+    }
+
+    async fn end_progress(&self, progress: IndexProgress) {
+        if let Some(token) = progress.token {
+            let progress = WorkDoneProgress::Done(WorkDoneProgressDone { message: None });
+            self.report_progress(&token, progress).await;
+        }
+    }
+
+    async fn report_progress(&self, token: &ProgressToken, progress: WorkDoneProgress) {
+        let params = ProgressParams {
+            token: token.clone(),
+            value: ProgressParamsValue::WorkDone(progress),
+        };
+        self.client.progress(params).await;
+    }
+
+    async fn update_document(
         &self,
         document: &Document,
         options: &Options,
@@ -517,7 +743,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         let path = document.uri.to_file_path().unwrap();
         let data = fs::metadata(&path).map_err(WorkspaceLoadError::IO)?;
         if data.modified().map_err(WorkspaceLoadError::IO)? > document.modified {
-            self.workspace_manager.load(&path, &options)
+            self.workspace_manager.load(&path, &options).await
         } else {
             Ok(())
         }
@@ -528,7 +754,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         let mut diagnostics_manager = self.diagnostics_manager.lock().await;
         let options = self.configuration(false).await;
 
-        for document in &workspace.documents {
+        for document in workspace.documents() {
             if document.uri.scheme() != "file" {
                 continue;
             }
@@ -549,17 +775,31 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         }
     }
 
-    async fn detect_root(&self, uri: Uri) {
+    async fn detect_root(&self, uri: Uri, options: &Options, progress: &mut IndexProgress) {
         if uri.scheme() == "file" {
             let mut path = uri.to_file_path().unwrap();
-            let options = self.configuration(false).await;
             while path.pop() {
+                let project_path = path.join(PROJECT_FILE_NAME);
+                let has_project = self
+                    .workspace_manager
+                    .get()
+                    .projects()
+                    .iter()
+                    .any(|project| project.directory == path);
+                if !has_project {
+                    if project_path.is_file() {
+                        let _ = self.workspace_manager.load_project(&project_path);
+                    } else if let Some(project) = Latexmkrc::read(&path).project(&path) {
+                        self.workspace_manager.add_project(project);
+                    }
+                }
+
                 let workspace = self.workspace_manager.get();
-                if workspace.find_parent(&uri, &options).is_some() {
+                if Self::has_root(&workspace, &uri, options) {
                     break;
                 }
 
-                for entry in WalkDir::new(&path)
+                let paths: Vec<_> = WalkDir::new(&path)
                     .min_depth(1)
                     .max_depth(1)
                     .into_iter()
@@ -573,27 +813,47 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
                             .and_then(Language::by_extension)
                             .is_some()
                     })
-                {
-                    if let Ok(parent_uri) = Uri::from_file_path(entry.path()) {
-                        if workspace.find(&parent_uri).is_none() {
-                            let _ = self.workspace_manager.load(entry.path(), &options);
-                        }
-                    }
-                }
+                    .filter(|entry| {
+                        Uri::from_file_path(entry.path())
+                            .map(|uri| workspace.find(&uri).is_none())
+                            .unwrap_or(false)
+                    })
+                    .map(|entry| entry.into_path())
+                    .collect();
+
+                drop(workspace);
+                self.index(paths, options, progress).await;
             }
+            self.detect_children(options, progress).await;
         }
     }
+
+    fn has_root(workspace: &Workspace, uri: &Uri, options: &Options) -> bool {
+        let has_magic_root = workspace.find(uri).map_or(false, |document| {
+            if let SyntaxTree::Latex(tree) = &document.tree {
+                tree.magic.root.is_some()
+            } else {
+                false
+            }
+        });
+
+        has_magic_root
+            || workspace.find_project(uri).is_some()
+            || workspace.find_parent(uri, options).is_some()
+    }
 }
 
 impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
     #[boxed]
     async fn before_message(&self) {
-        self.detect_children().await;
+        self.action_manager.push(Action::DetectChildren);
+        self.workspace_manager.update_projects();
+        self.update_workspace_options().await;
 
         let options = self.configuration(false).await;
         let workspace = self.workspace_manager.get();
-        for document in &workspace.documents {
-            let _ = self.update_document(document, &options);
+        for document in workspace.documents() {
+            let _ = self.update_document(document, &options).await;
         }
     }
 
@@ -653,26 +913,43 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
                     };
                 }
                 Action::LoadConfiguration => {
+                    let _lock = self.index_lock.lock().await;
                     let options = self.configuration(true).await;
-                    let workspace = self.workspace_manager.get();
-                    for document in &workspace.documents {
-                        if let Ok(path) = document.uri.to_file_path() {
-                            let _ = self.workspace_manager.load(&path, &options);
-                        }
-                    }
+                    let paths = self
+                        .workspace_manager
+                        .get()
+                        .documents()
+                        .iter()
+                        .filter_map(|document| document.uri.to_file_path().ok())
+                        .collect();
+                    let mut progress = IndexProgress::default();
+                    self.index(paths, &options, &mut progress).await;
+                    self.end_progress(progress).await;
                 }
                 Action::UpdateConfiguration(settings) => {
                     self.config_strategy.get().unwrap().set(settings).await;
                 }
                 Action::DetectRoot(uri) => {
-                    self.detect_root(uri).await;
+                    let _lock = self.index_lock.lock().await;
+                    let options = self.configuration(false).await;
+                    let mut progress = IndexProgress::default();
+                    self.detect_root(uri, &options, &mut progress).await;
+                    self.end_progress(progress).await;
+                }
+                Action::DetectChildren => {
+                    let _lock = self.index_lock.lock().await;
+                    let options = self.configuration(false).await;
+                    let mut progress = IndexProgress::default();
+                    self.detect_children(&options, &mut progress).await;
+                    self.end_progress(progress).await;
                 }
                 Action::PublishDiagnostics => {
+                    let options = self.configuration(false).await;
                     let workspace = self.workspace_manager.get();
-                    for document in &workspace.documents {
+                    for document in workspace.documents() {
                         let diagnostics = {
                             let manager = self.diagnostics_manager.lock().await;
-                            manager.get(&document)
+                            manager.get(&workspace, &document, &options)
                         };
 
                         let params = PublishDiagnosticsParams {
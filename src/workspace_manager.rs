@@ -1,14 +1,18 @@
-use futures::executor::block_on;
+use futures::prelude::*;
+use futures::stream::FuturesUnordered;
 use log::*;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::Mutex;
-use texlab_distro::{Distribution, Language};
+use std::time::SystemTime;
+use texlab_distro::{Distribution, Language, Resolver};
 use texlab_protocol::{Options, TextDocumentItem, Uri};
 use texlab_syntax::SyntaxTree;
-use texlab_workspace::{Document, Workspace};
+use texlab_workspace::{Document, DocumentCache, Project, Workspace};
+use tokio::task;
 
 #[derive(Debug)]
 pub enum WorkspaceLoadError {
@@ -20,13 +24,19 @@ pub enum WorkspaceLoadError {
 pub struct WorkspaceManager {
     distribution: Arc<Box<dyn Distribution>>,
     workspace: Mutex<Arc<Workspace>>,
+    cache_directory: Option<PathBuf>,
+    document_cache: Mutex<Option<Arc<DocumentCache>>>,
+    project_files: Mutex<HashMap<PathBuf, SystemTime>>,
 }
 
 impl WorkspaceManager {
-    pub fn new(distribution: Arc<Box<dyn Distribution>>) -> Self {
+    pub fn new(distribution: Arc<Box<dyn Distribution>>, cache_directory: Option<PathBuf>) -> Self {
         Self {
             distribution,
             workspace: Mutex::default(),
+            cache_directory,
+            document_cache: Mutex::default(),
+            project_files: Mutex::default(),
         }
     }
 
@@ -35,7 +45,7 @@ impl WorkspaceManager {
         Arc::clone(&workspace)
     }
 
-    pub fn add(&self, document: TextDocumentItem, options: &Options) {
+    pub async fn add(&self, document: TextDocumentItem, options: &Options) {
         let language = match Language::by_language_id(&document.language_id) {
             Some(language) => language,
             None => {
@@ -44,17 +54,90 @@ impl WorkspaceManager {
             }
         };
 
-        let mut workspace = self.workspace.lock().unwrap();
-        *workspace = self.add_or_update(
-            &workspace,
+        let resolver = self.distribution.resolver().await;
+        let document = Document::parse(
             document.uri.into(),
             document.text,
             language,
             options,
+            &resolver,
         );
+        self.insert(document);
+    }
+
+    pub async fn load(&self, path: &Path, options: &Options) -> Result<(), WorkspaceLoadError> {
+        let resolver = self.distribution.resolver().await;
+        let cache = self.document_cache(options, &resolver);
+        let document = Self::parse_file(path, options, &resolver, cache.as_deref())?;
+        self.insert(document);
+        Ok(())
+    }
+
+    pub async fn load_all<F, R>(
+        &self,
+        paths: Vec<PathBuf>,
+        options: &Options,
+        mut progress: F,
+    ) -> usize
+    where
+        F: FnMut(usize, usize) -> R,
+        R: Future<Output = ()>,
+    {
+        let resolver = self.distribution.resolver().await;
+        let cache = self.document_cache(options, &resolver);
+        let total = paths.len();
+        let mut tasks: FuturesUnordered<_> = paths
+            .into_iter()
+            .map(|path| {
+                let options = options.clone();
+                let resolver = Arc::clone(&resolver);
+                let cache = cache.clone();
+                task::spawn_blocking(move || {
+                    Self::parse_file(&path, &options, &resolver, cache.as_deref())
+                })
+            })
+            .collect();
+
+        let mut loaded = 0;
+        let mut done = 0;
+        while let Some(result) = tasks.next().await {
+            done += 1;
+            if let Ok(Ok(document)) = result {
+                self.insert(document);
+                loaded += 1;
+            }
+            progress(done, total).await;
+        }
+        loaded
     }
 
-    pub fn load(&self, path: &Path, options: &Options) -> Result<(), WorkspaceLoadError> {
+    fn document_cache(
+        &self,
+        options: &Options,
+        resolver: &Arc<Resolver>,
+    ) -> Option<Arc<DocumentCache>> {
+        let directory = self.cache_directory.as_ref()?;
+        let mut cache = self.document_cache.lock().unwrap();
+        if !cache
+            .as_ref()
+            .map_or(false, |cache| cache.is_valid(options, resolver))
+        {
+            let resolver = Arc::clone(resolver);
+            let new_cache = DocumentCache::new(directory, options, resolver);
+            if cache.is_none() {
+                new_cache.prune();
+            }
+            *cache = Some(Arc::new(new_cache));
+        }
+        cache.clone()
+    }
+
+    fn parse_file(
+        path: &Path,
+        options: &Options,
+        resolver: &Resolver,
+        cache: Option<&DocumentCache>,
+    ) -> Result<Document, WorkspaceLoadError> {
         let language = match path
             .extension()
             .and_then(OsStr::to_str)
@@ -83,48 +166,97 @@ impl WorkspaceManager {
             }
         };
 
-        let mut workspace = self.workspace.lock().unwrap();
-        *workspace = self.add_or_update(&workspace, uri, text, language, options);
+        let document = match cache {
+            Some(cache) => cache.parse(uri, text, language),
+            None => Document::parse(uri, text, language, options, resolver),
+        };
+        Ok(document)
+    }
+
+    pub fn load_project(&self, path: &Path) -> Result<(), WorkspaceLoadError> {
+        let modified = fs::metadata(path)
+            .and_then(|data| data.modified())
+            .map_err(WorkspaceLoadError::IO)?;
+
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(why) => {
+                warn!("Could not open project file: {}", path.to_string_lossy());
+                return Err(WorkspaceLoadError::IO(why));
+            }
+        };
+
+        let project = match Project::parse(path, &text) {
+            Some(project) => project,
+            None => {
+                error!("Invalid path: {}", path.to_string_lossy());
+                return Err(WorkspaceLoadError::InvalidPath);
+            }
+        };
+
+        self.add_project(project);
+        let mut project_files = self.project_files.lock().unwrap();
+        project_files.insert(path.to_owned(), modified);
         Ok(())
     }
 
-    pub fn update(&self, uri: Uri, text: String, options: &Options) {
+    pub fn update_projects(&self) {
+        let project_files: Vec<_> = {
+            let project_files = self.project_files.lock().unwrap();
+            project_files
+                .iter()
+                .map(|(path, modified)| (path.clone(), *modified))
+                .collect()
+        };
+
+        for (path, modified) in project_files {
+            match fs::metadata(&path).and_then(|data| data.modified()) {
+                Ok(time) if time > modified => {
+                    let _ = self.load_project(&path);
+                }
+                Ok(_) => (),
+                Err(_) => {
+                    self.project_files.lock().unwrap().remove(&path);
+                    if let Some(directory) = path.parent() {
+                        let mut workspace = self.workspace.lock().unwrap();
+                        Arc::make_mut(&mut *workspace).remove_project(directory);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn add_project(&self, project: Project) {
         let mut workspace = self.workspace.lock().unwrap();
+        Arc::make_mut(&mut *workspace).add_project(project);
+    }
 
-        let old_document = match workspace.documents.iter().find(|x| x.uri == uri) {
-            Some(document) => document,
+    pub async fn update(&self, uri: Uri, text: String, options: &Options) {
+        let language = match self.get().find(&uri) {
+            Some(document) => match document.tree {
+                SyntaxTree::Latex(_) => Language::Latex,
+                SyntaxTree::Bibtex(_) => Language::Bibtex,
+            },
             None => {
                 warn!("Document not found: {}", uri);
                 return;
             }
         };
 
-        let language = match old_document.tree {
-            SyntaxTree::Latex(_) => Language::Latex,
-            SyntaxTree::Bibtex(_) => Language::Bibtex,
-        };
-
-        *workspace = self.add_or_update(&workspace, uri, text, language, options);
+        let resolver = self.distribution.resolver().await;
+        let document = Document::parse(uri, text, language, options, &resolver);
+        self.insert(document);
     }
 
-    fn add_or_update(
-        &self,
-        workspace: &Workspace,
-        uri: Uri,
-        text: String,
-        language: Language,
-        options: &Options,
-    ) -> Arc<Workspace> {
-        let resolver = block_on(self.distribution.resolver());
-        let document = Document::parse(uri, text, language, &options, &resolver);
-        let mut documents: Vec<Arc<Document>> = workspace
-            .documents
-            .iter()
-            .filter(|x| x.uri != document.uri)
-            .cloned()
-            .collect();
+    pub fn update_options(&self, options: &Options, project_options: HashMap<PathBuf, Options>) {
+        let mut workspace = self.workspace.lock().unwrap();
+        if workspace.options() != options || *workspace.project_options() != project_options {
+            Arc::make_mut(&mut *workspace).set_options(options, project_options);
+        }
+    }
 
-        documents.push(Arc::new(document));
-        Arc::new(Workspace { documents })
+    fn insert(&self, document: Document) {
+        let mut workspace = self.workspace.lock().unwrap();
+        Arc::make_mut(&mut *workspace).add(Arc::new(document));
     }
 }
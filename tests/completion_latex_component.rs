@@ -11,8 +11,8 @@ async fn kernel_command() {
 
 #[tokio::test]
 async fn kernel_command_glyph() {
-    let item = run_item(SCENARIO, "foo.tex", 7, 7, "varepsilon").await;
-    verify::text_edit(&item, 7, 1, 7, 7, "varepsilon");
+    let item = run_item(SCENARIO, "foo.tex", 7, 8, "varepsilon").await;
+    verify::text_edit(&item, 7, 2, 7, 8, "varepsilon");
     verify::detail(&item, "ε, built-in");
 }
 
@@ -18,6 +18,7 @@ async fn infinite_line_length() {
         "infinite_line_length/unformatted.bib",
         Some(BibtexFormattingOptions {
             line_length: Some(0),
+            ..BibtexFormattingOptions::default()
         }),
     )
     .await;
@@ -28,3 +29,15 @@ async fn infinite_line_length() {
     );
     assert_eq!(edits[0].range, Range::new_simple(0, 0, 0, 149));
 }
+
+#[tokio::test]
+async fn range() {
+    let (scenario, edits) =
+        run_bibtex_range("range/unformatted.bib", Range::new_simple(2, 5, 2, 5)).await;
+    assert_eq!(edits.len(), 1);
+    assert_eq!(
+        edits[0].new_text,
+        scenario.read("range/formatted.bib").await
+    );
+    assert_eq!(edits[0].range, Range::new_simple(2, 0, 2, 32));
+}